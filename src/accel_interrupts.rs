@@ -0,0 +1,306 @@
+use crate::{
+    interface::{ReadData, WriteData},
+    register_address::{
+        ActDurA, ActThsA, ClickSrcA, ClickThsA, CtrlReg5A, CtrlReg6A, Int1CfgA, Int1DurationA,
+        Int1SrcA, Int1ThsA, Int2CfgA, Int2DurationA, Int2SrcA, Int2ThsA, TimeLatencyA, TimeLimitA,
+        TimeWindowA,
+    },
+    ClickConfig, ClickSource, Error, Interrupt, InterruptConfig, InterruptMode, InterruptSource,
+    Lsm303agr,
+};
+
+impl<DI, CommE, PinE, MODE> Lsm303agr<DI, MODE>
+where
+    DI: ReadData<Error = Error<CommE, PinE>> + WriteData<Error = Error<CommE, PinE>>,
+{
+    /// Configure the accelerometer inertial interrupt generator 1 (AOI1).
+    ///
+    /// The threshold is converted using the current accelerometer scale and
+    /// the duration using the current output data rate.
+    ///
+    /// Returns `Error::InvalidInputData` if a duration is given while the
+    /// accelerometer is powered down.
+    ///
+    /// To route the interrupt to the INT1 pin, use
+    /// [`acc_enable_interrupt(Interrupt::Aoi1)`](Lsm303agr::acc_enable_interrupt).
+    pub fn acc_configure_int1(
+        &mut self,
+        config: InterruptConfig,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let (threshold, duration) = self.aoi_threshold_and_duration(&config)?;
+
+        self.iface
+            .write_accel_register(Int1ThsA::from_bits_truncate(threshold))?;
+        self.iface
+            .write_accel_register(Int1DurationA::from_bits_truncate(duration))?;
+        self.iface
+            .write_accel_register(Int1CfgA::from_bits_truncate(config.cfg_bits()))
+    }
+
+    /// Configure the accelerometer inertial interrupt generator 2 (AOI2).
+    ///
+    /// The threshold is converted using the current accelerometer scale and
+    /// the duration using the current output data rate.
+    ///
+    /// Returns `Error::InvalidInputData` if a duration is given while the
+    /// accelerometer is powered down.
+    ///
+    /// To route the interrupt to the INT1 pin, use
+    /// [`acc_enable_interrupt(Interrupt::Aoi2)`](Lsm303agr::acc_enable_interrupt).
+    pub fn acc_configure_int2(
+        &mut self,
+        config: InterruptConfig,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let (threshold, duration) = self.aoi_threshold_and_duration(&config)?;
+
+        self.iface
+            .write_accel_register(Int2ThsA::from_bits_truncate(threshold))?;
+        self.iface
+            .write_accel_register(Int2DurationA::from_bits_truncate(duration))?;
+        self.iface
+            .write_accel_register(Int2CfgA::from_bits_truncate(config.cfg_bits()))
+    }
+
+    /// Enable free-fall detection on the INT1 pin.
+    ///
+    /// This configures the inertial interrupt generator 1 (AOI1) to trigger
+    /// when the acceleration on all axes stays below `threshold_mg` for at
+    /// least `duration_ms` and routes it to the INT1 pin.
+    ///
+    /// The threshold is converted using the current accelerometer scale and
+    /// the duration using the current output data rate, so these should be
+    /// set beforehand. Typical values are a threshold of 350 m*g* and a
+    /// duration of 30 ms.
+    ///
+    /// Returns `Error::InvalidInputData` if a duration is given while the
+    /// accelerometer is powered down.
+    pub fn acc_enable_free_fall_detection(
+        &mut self,
+        threshold_mg: u16,
+        duration_ms: u16,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let config = InterruptConfig::default()
+            .mode(InterruptMode::And)
+            .x_low(true)
+            .y_low(true)
+            .z_low(true)
+            .threshold_mg(threshold_mg)
+            .duration_ms(duration_ms);
+
+        self.acc_configure_int1(config)?;
+        self.acc_enable_interrupt(Interrupt::Aoi1)
+    }
+
+    /// Get the accelerometer inertial interrupt generator 1 (AOI1) source.
+    ///
+    /// Reading the source clears a latched interrupt.
+    pub fn acc_int1_source(&mut self) -> Result<InterruptSource, Error<CommE, PinE>> {
+        self.iface
+            .read_accel_register::<Int1SrcA>()
+            .map(InterruptSource::new)
+    }
+
+    /// Get the accelerometer inertial interrupt generator 2 (AOI2) source.
+    ///
+    /// Reading the source clears a latched interrupt.
+    pub fn acc_int2_source(&mut self) -> Result<InterruptSource, Error<CommE, PinE>> {
+        self.iface
+            .read_accel_register::<Int2SrcA>()
+            .map(InterruptSource::new)
+    }
+
+    /// Get and clear the latched accelerometer inertial interrupt generator 1
+    /// (AOI1) source.
+    ///
+    /// Returns `None` if no interrupt is active. This can be used to find out
+    /// which events triggered a latched interrupt, e.g. after waking up.
+    pub fn acc_take_int1_source(&mut self) -> Result<Option<InterruptSource>, Error<CommE, PinE>> {
+        let source = self.acc_int1_source()?;
+        Ok(if source.active() { Some(source) } else { None })
+    }
+
+    /// Get and clear the latched accelerometer inertial interrupt generator 2
+    /// (AOI2) source.
+    ///
+    /// Returns `None` if no interrupt is active. This can be used to find out
+    /// which events triggered a latched interrupt, e.g. after waking up.
+    pub fn acc_take_int2_source(&mut self) -> Result<Option<InterruptSource>, Error<CommE, PinE>> {
+        let source = self.acc_int2_source()?;
+        Ok(if source.active() { Some(source) } else { None })
+    }
+
+    /// Latch (`true`) or pulse (`false`) the accelerometer inertial interrupt
+    /// generator 1 (AOI1) interrupt.
+    ///
+    /// A latched interrupt is cleared by reading the source, e.g. with
+    /// [`acc_take_int1_source()`](Lsm303agr::acc_take_int1_source).
+    pub fn acc_set_int1_latched(&mut self, latched: bool) -> Result<(), Error<CommE, PinE>> {
+        self.update_ctrl_reg5_a(CtrlReg5A::LIR_INT1, latched)
+    }
+
+    /// Latch (`true`) or pulse (`false`) the accelerometer inertial interrupt
+    /// generator 2 (AOI2) interrupt.
+    ///
+    /// A latched interrupt is cleared by reading the source, e.g. with
+    /// [`acc_take_int2_source()`](Lsm303agr::acc_take_int2_source).
+    pub fn acc_set_int2_latched(&mut self, latched: bool) -> Result<(), Error<CommE, PinE>> {
+        self.update_ctrl_reg5_a(CtrlReg5A::LIR_INT2, latched)
+    }
+
+    /// Enable/disable 4D detection on the accelerometer inertial interrupt
+    /// generator 1 (AOI1).
+    ///
+    /// When enabled, the Z axis is ignored by the 6D movement and position
+    /// modes.
+    pub fn acc_set_int1_4d(&mut self, enabled: bool) -> Result<(), Error<CommE, PinE>> {
+        self.update_ctrl_reg5_a(CtrlReg5A::D4D_INT1, enabled)
+    }
+
+    /// Enable/disable 4D detection on the accelerometer inertial interrupt
+    /// generator 2 (AOI2).
+    ///
+    /// When enabled, the Z axis is ignored by the 6D movement and position
+    /// modes.
+    pub fn acc_set_int2_4d(&mut self, enabled: bool) -> Result<(), Error<CommE, PinE>> {
+        self.update_ctrl_reg5_a(CtrlReg5A::D4D_INT2, enabled)
+    }
+
+    /// Configure accelerometer click (tap) detection.
+    ///
+    /// The threshold is converted using the current accelerometer scale and
+    /// the time limit, latency and window using the current output data rate.
+    ///
+    /// Returns `Error::InvalidInputData` if a time is given while the
+    /// accelerometer is powered down.
+    ///
+    /// To route the interrupt to the INT1 pin, use
+    /// [`acc_enable_interrupt(Interrupt::Click)`](Lsm303agr::acc_enable_interrupt).
+    pub fn acc_configure_click(&mut self, config: ClickConfig) -> Result<(), Error<CommE, PinE>> {
+        let threshold = self
+            .get_accel_scale()
+            .threshold_from_mg(config.threshold_mg);
+        let time_limit = self.accel_periods_from_ms(config.time_limit_ms.into(), 0x7F)?;
+        let time_latency = self.accel_periods_from_ms(config.time_latency_ms.into(), 0xFF)?;
+        let time_window = self.accel_periods_from_ms(config.time_window_ms.into(), 0xFF)?;
+
+        let mut ths = ClickThsA::from_bits_truncate(threshold);
+        ths.set(ClickThsA::LIR_CLICK, config.latched);
+
+        self.iface.write_accel_register(ths)?;
+        self.iface
+            .write_accel_register(TimeLimitA::from_bits_truncate(time_limit as u8))?;
+        self.iface
+            .write_accel_register(TimeLatencyA::from_bits_truncate(time_latency as u8))?;
+        self.iface
+            .write_accel_register(TimeWindowA::from_bits_truncate(time_window as u8))?;
+        self.iface.write_accel_register(config.cfg())
+    }
+
+    /// Get the accelerometer click (tap) source.
+    ///
+    /// Reading the source clears a latched interrupt.
+    pub fn acc_click_source(&mut self) -> Result<ClickSource, Error<CommE, PinE>> {
+        self.iface
+            .read_accel_register::<ClickSrcA>()
+            .map(ClickSource::new)
+    }
+
+    /// Enable accelerometer sleep-to-wake and return-to-sleep detection.
+    ///
+    /// Once the acceleration stays below `threshold_mg` for `duration_s`
+    /// seconds, the accelerometer automatically switches to 10 Hz low-power
+    /// mode. As soon as the threshold is exceeded, the previously configured
+    /// mode and output data rate are restored.
+    ///
+    /// The threshold is converted using the current accelerometer scale and
+    /// the duration using the current output data rate. A threshold below
+    /// the register resolution disables the detection.
+    ///
+    /// The duration can only be set in steps of 8/ODR, e.g. 8 s at 1 Hz, and
+    /// is rounded to the nearest step. It is limited to 2041/ODR.
+    ///
+    /// Returns `Error::InvalidInputData` if a duration is given while the
+    /// accelerometer is powered down.
+    ///
+    /// To route the activity state to the INT2 pin, use
+    /// [`acc_enable_activity_interrupt()`](Lsm303agr::acc_enable_activity_interrupt).
+    pub fn acc_enable_activity_detection(
+        &mut self,
+        threshold_mg: u16,
+        duration_s: u16,
+    ) -> Result<(), Error<CommE, PinE>> {
+        // Duration is (8 * ACT_DUR + 1) / ODR.
+        let periods = self.accel_periods_from_ms(u32::from(duration_s) * 1000, 8 * 0xFF + 1)?;
+        let duration = (periods + 3) / 8;
+        let threshold = self.get_accel_scale().threshold_from_mg(threshold_mg);
+
+        self.iface
+            .write_accel_register(ActDurA::from_bits_truncate(duration as u8))?;
+        self.iface
+            .write_accel_register(ActThsA::from_bits_truncate(threshold))
+    }
+
+    /// Disable accelerometer sleep-to-wake and return-to-sleep detection.
+    pub fn acc_disable_activity_detection(&mut self) -> Result<(), Error<CommE, PinE>> {
+        self.iface.write_accel_register(ActThsA::empty())
+    }
+
+    /// Route the accelerometer activity state to the INT2 pin.
+    pub fn acc_enable_activity_interrupt(&mut self) -> Result<(), Error<CommE, PinE>> {
+        let reg6 = self.ctrl_reg6_a.union(CtrlReg6A::P2_ACT);
+        self.iface.write_accel_register(reg6)?;
+        self.ctrl_reg6_a = reg6;
+
+        Ok(())
+    }
+
+    /// Stop routing the accelerometer activity state to the INT2 pin.
+    pub fn acc_disable_activity_interrupt(&mut self) -> Result<(), Error<CommE, PinE>> {
+        let reg6 = self.ctrl_reg6_a.difference(CtrlReg6A::P2_ACT);
+        self.iface.write_accel_register(reg6)?;
+        self.ctrl_reg6_a = reg6;
+
+        Ok(())
+    }
+
+    fn update_ctrl_reg5_a(
+        &mut self,
+        flags: CtrlReg5A,
+        value: bool,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let mut reg5 = self.ctrl_reg5_a;
+        reg5.set(flags, value);
+        self.iface.write_accel_register(reg5)?;
+        self.ctrl_reg5_a = reg5;
+
+        Ok(())
+    }
+
+    fn aoi_threshold_and_duration(
+        &self,
+        config: &InterruptConfig,
+    ) -> Result<(u8, u8), Error<CommE, PinE>> {
+        let threshold = self
+            .get_accel_scale()
+            .threshold_from_mg(config.threshold_mg);
+        let duration = self.accel_periods_from_ms(config.duration_ms.into(), 0x7F)?;
+
+        Ok((threshold, duration as u8))
+    }
+
+    /// Convert the given time into 1/ODR periods, clamped to `max`.
+    pub(crate) fn accel_periods_from_ms(
+        &self,
+        ms: u32,
+        max: u32,
+    ) -> Result<u32, Error<CommE, PinE>> {
+        if ms == 0 {
+            return Ok(0);
+        }
+
+        match self.accel_odr {
+            Some(odr) => Ok(odr.periods_from_ms(ms).min(max)),
+            None => Err(Error::InvalidInputData),
+        }
+    }
+}
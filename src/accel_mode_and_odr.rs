@@ -3,7 +3,7 @@ use embedded_hal::blocking::delay::DelayUs;
 use crate::{
     interface::{ReadData, WriteData},
     register_address::{CtrlReg1A, CtrlReg4A},
-    AccelMode, AccelOutputDataRate, AccelScale, Error, Lsm303agr,
+    AccelMode, AccelOutputDataRate, AccelScale, AxisSet, Error, Lsm303agr,
 };
 
 impl<DI, CommE, PinE, MODE> Lsm303agr<DI, MODE>
@@ -15,6 +15,9 @@ where
     /// Returns `Error::InvalidInputData` if the mode is incompatible with
     /// the given output data rate.
     ///
+    /// Leaving high-resolution mode switches the output data back to
+    /// little-endian byte order.
+    ///
     #[doc = include_str!("delay.md")]
     pub fn set_accel_mode_and_odr<D: DelayUs<u32>>(
         &mut self,
@@ -23,24 +26,7 @@ where
         odr: impl Into<Option<AccelOutputDataRate>>,
     ) -> Result<(), Error<CommE, PinE>> {
         let odr = odr.into();
-
-        check_accel_odr_is_compatible_with_mode(odr, mode)?;
-
-        let old_mode = self.get_accel_mode();
-
-        let mut reg1 = self.ctrl_reg1_a.difference(CtrlReg1A::ODR);
-
-        if let Some(odr) = odr {
-            reg1 = reg1.with_odr(odr);
-        }
-
-        let reg1 = if mode == AccelMode::LowPower {
-            reg1.union(CtrlReg1A::LPEN)
-        } else {
-            reg1.difference(CtrlReg1A::LPEN)
-        };
-
-        let reg4 = self.ctrl_reg4_a.difference(CtrlReg4A::HR);
+        let (reg1, reg4, change_time) = self.accel_mode_and_odr_registers(mode, odr)?;
 
         if mode != AccelMode::HighResolution {
             self.iface.write_accel_register(reg4)?;
@@ -52,19 +38,42 @@ where
         self.accel_odr = odr;
 
         if mode == AccelMode::HighResolution {
-            let reg4 = reg4.union(CtrlReg4A::HR);
             self.iface.write_accel_register(reg4)?;
             self.ctrl_reg4_a = reg4;
         }
 
-        if let Some(odr) = self.accel_odr {
-            let change_time = old_mode.change_time_us(mode, odr);
+        if let Some(change_time) = change_time {
             delay.delay_us(change_time);
         }
 
         Ok(())
     }
 
+    /// Set accelerometer scaling factor
+    ///
+    /// This changes the scale at which the acceleration is read.
+    /// `AccelScale::G2` for example can return values between -2g and +2g
+    /// where g is the gravity of the earth (~9.82 m/s²).
+    pub fn set_accel_scale(&mut self, scale: AccelScale) -> Result<(), Error<CommE, PinE>> {
+        let reg4 = self.ctrl_reg4_a.with_scale(scale);
+        self.iface.write_accel_register(reg4)?;
+        self.ctrl_reg4_a = reg4;
+        Ok(())
+    }
+
+    /// Enable/disable accelerometer axes.
+    ///
+    /// Disabled axes are not measured, which reduces power consumption.
+    /// See: [`Acceleration::axes()`](crate::Acceleration::axes).
+    pub fn set_accel_axes(&mut self, axes: AxisSet) -> Result<(), Error<CommE, PinE>> {
+        let reg1 = self.ctrl_reg1_a.with_axes(axes);
+        self.iface.write_accel_register(reg1)?;
+        self.ctrl_reg1_a = reg1;
+        Ok(())
+    }
+}
+
+impl<DI, MODE> Lsm303agr<DI, MODE> {
     /// Get the accelerometer mode
     pub fn get_accel_mode(&mut self) -> AccelMode {
         let power_down = self.ctrl_reg1_a.intersection(CtrlReg1A::ODR).is_empty();
@@ -82,22 +91,53 @@ where
         }
     }
 
-    /// Set accelerometer scaling factor
-    ///
-    /// This changes the scale at which the acceleration is read.
-    /// `AccelScale::G2` for example can return values between -2g and +2g
-    /// where g is the gravity of the earth (~9.82 m/s²).
-    pub fn set_accel_scale(&mut self, scale: AccelScale) -> Result<(), Error<CommE, PinE>> {
-        let reg4 = self.ctrl_reg4_a.with_scale(scale);
-        self.iface.write_accel_register(reg4)?;
-        self.ctrl_reg4_a = reg4;
-        Ok(())
-    }
-
     /// Get accelerometer scaling factor
     pub fn get_accel_scale(&self) -> AccelScale {
         self.ctrl_reg4_a.scale()
     }
+
+    /// Get enabled accelerometer axes
+    pub fn get_accel_axes(&self) -> AxisSet {
+        self.ctrl_reg1_a.axes()
+    }
+
+    /// Compute the registers for changing the accelerometer mode and output
+    /// data rate, and the time to wait for the change.
+    ///
+    /// `CTRL_REG4_A` needs to be written before `CTRL_REG1_A`, except when
+    /// switching to high-resolution mode, where it needs to be written after.
+    pub(crate) fn accel_mode_and_odr_registers<CommE, PinE>(
+        &mut self,
+        mode: AccelMode,
+        odr: Option<AccelOutputDataRate>,
+    ) -> Result<(CtrlReg1A, CtrlReg4A, Option<u32>), Error<CommE, PinE>> {
+        check_accel_odr_is_compatible_with_mode(odr, mode)?;
+
+        let old_mode = self.get_accel_mode();
+
+        let mut reg1 = self.ctrl_reg1_a.difference(CtrlReg1A::ODR);
+
+        if let Some(odr) = odr {
+            reg1 = reg1.with_odr(odr);
+        }
+
+        let reg1 = if mode == AccelMode::LowPower {
+            reg1.union(CtrlReg1A::LPEN)
+        } else {
+            reg1.difference(CtrlReg1A::LPEN)
+        };
+
+        let reg4 = if mode == AccelMode::HighResolution {
+            self.ctrl_reg4_a.union(CtrlReg4A::HR)
+        } else {
+            // Big-endian output is only available in high-resolution mode.
+            self.ctrl_reg4_a.difference(CtrlReg4A::HR | CtrlReg4A::BLE)
+        };
+
+        let change_time = odr.map(|odr| old_mode.change_time_us(mode, odr));
+
+        Ok((reg1, reg4, change_time))
+    }
 }
 
 fn check_accel_odr_is_compatible_with_mode<CommE, PinE>(
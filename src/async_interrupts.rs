@@ -0,0 +1,311 @@
+use core::convert::Infallible;
+
+use embedded_hal_async::digital::Wait;
+
+use crate::{
+    interface::{AsyncReadData, AsyncWriteData},
+    register_address::{CfgRegCM, ClickSrcA, CtrlReg6A, FifoSrcRegA, StatusRegA, StatusRegM},
+    ClickSource, Error, FifoStatus, Interrupt, InterruptPin, InterruptPolarity, Lsm303agr,
+    Lsm303agrAsync, Status,
+};
+
+impl<DI, MODE, INT2, INTMAG> Lsm303agrAsync<DI, MODE, (), INT2, INTMAG> {
+    /// Attach the accelerometer INT1 pin.
+    pub fn with_int1_pin<P>(self, pin: P) -> Lsm303agrAsync<DI, MODE, P, INT2, INTMAG> {
+        Lsm303agrAsync {
+            dev: self.dev,
+            int1: pin,
+            int2: self.int2,
+            int_mag: self.int_mag,
+        }
+    }
+}
+
+impl<DI, MODE, INT1, INTMAG> Lsm303agrAsync<DI, MODE, INT1, (), INTMAG> {
+    /// Attach the accelerometer INT2 pin.
+    pub fn with_int2_pin<P>(self, pin: P) -> Lsm303agrAsync<DI, MODE, INT1, P, INTMAG> {
+        Lsm303agrAsync {
+            dev: self.dev,
+            int1: self.int1,
+            int2: pin,
+            int_mag: self.int_mag,
+        }
+    }
+}
+
+impl<DI, MODE, INT1, INT2> Lsm303agrAsync<DI, MODE, INT1, INT2, ()> {
+    /// Attach the magnetometer INT_MAG/DRDY pin.
+    pub fn with_int_mag_pin<P>(self, pin: P) -> Lsm303agrAsync<DI, MODE, INT1, INT2, P> {
+        Lsm303agrAsync {
+            dev: self.dev,
+            int1: self.int1,
+            int2: self.int2,
+            int_mag: pin,
+        }
+    }
+}
+
+impl<DI, MODE, INT1, INT2, INTMAG> Lsm303agrAsync<DI, MODE, INT1, INT2, INTMAG> {
+    /// Release the attached INT1, INT2 and INT_MAG/DRDY pins.
+    pub fn release_pins(self) -> (Lsm303agrAsync<DI, MODE>, INT1, INT2, INTMAG) {
+        (
+            Lsm303agrAsync {
+                dev: self.dev,
+                int1: (),
+                int2: (),
+                int_mag: (),
+            },
+            self.int1,
+            self.int2,
+            self.int_mag,
+        )
+    }
+
+    /// Access the blocking driver sharing this driver's configuration.
+    ///
+    /// This allows using the blocking configuration methods which have no
+    /// asynchronous equivalent, if the bus also implements the blocking
+    /// embedded-hal 1.0 traits.
+    pub fn blocking(&mut self) -> &mut Lsm303agr<DI, MODE> {
+        &mut self.dev
+    }
+}
+
+impl<DI, CommE, PinE, MODE, INT1, INT2, INTMAG> Lsm303agrAsync<DI, MODE, INT1, INT2, INTMAG>
+where
+    DI: AsyncReadData<Error = Error<CommE, PinE>> + AsyncWriteData<Error = Error<CommE, PinE>>,
+{
+    /// Enable accelerometer interrupt on the INT1 pin.
+    pub async fn acc_enable_interrupt(
+        &mut self,
+        interrupt: Interrupt,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let reg3 = self.dev.ctrl_reg3_a.with_interrupt(interrupt);
+        self.dev.iface.write_accel_register(reg3).await?;
+        self.dev.ctrl_reg3_a = reg3;
+
+        Ok(())
+    }
+
+    /// Disable accelerometer interrupt on the INT1 pin.
+    pub async fn acc_disable_interrupt(
+        &mut self,
+        interrupt: Interrupt,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let reg3 = self.dev.ctrl_reg3_a.without_interrupt(interrupt);
+        self.dev.iface.write_accel_register(reg3).await?;
+        self.dev.ctrl_reg3_a = reg3;
+
+        Ok(())
+    }
+
+    /// Enable accelerometer interrupt on the given pin.
+    ///
+    /// Only [`Interrupt::Click`], [`Interrupt::Aoi1`] and [`Interrupt::Aoi2`]
+    /// can be routed to the INT2 pin. Returns `Error::InvalidInputData` for
+    /// any other interrupt on INT2.
+    pub async fn acc_enable_interrupt_on(
+        &mut self,
+        pin: InterruptPin,
+        interrupt: Interrupt,
+    ) -> Result<(), Error<CommE, PinE>> {
+        match pin {
+            InterruptPin::Int1 => self.acc_enable_interrupt(interrupt).await,
+            InterruptPin::Int2 => {
+                let reg6 = self
+                    .dev
+                    .ctrl_reg6_a
+                    .with_interrupt(interrupt)
+                    .ok_or(Error::InvalidInputData)?;
+                self.dev.iface.write_accel_register(reg6).await?;
+                self.dev.ctrl_reg6_a = reg6;
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Disable accelerometer interrupt on the given pin.
+    ///
+    /// Returns `Error::InvalidInputData` for interrupts which cannot be routed
+    /// to the INT2 pin.
+    pub async fn acc_disable_interrupt_on(
+        &mut self,
+        pin: InterruptPin,
+        interrupt: Interrupt,
+    ) -> Result<(), Error<CommE, PinE>> {
+        match pin {
+            InterruptPin::Int1 => self.acc_disable_interrupt(interrupt).await,
+            InterruptPin::Int2 => {
+                let reg6 = self
+                    .dev
+                    .ctrl_reg6_a
+                    .without_interrupt(interrupt)
+                    .ok_or(Error::InvalidInputData)?;
+                self.dev.iface.write_accel_register(reg6).await?;
+                self.dev.ctrl_reg6_a = reg6;
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Set the polarity of the accelerometer interrupt pins.
+    pub async fn acc_set_interrupt_polarity(
+        &mut self,
+        polarity: InterruptPolarity,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let reg6 = self.dev.ctrl_reg6_a.with_polarity(polarity);
+        self.dev.iface.write_accel_register(reg6).await?;
+        self.dev.ctrl_reg6_a = reg6;
+
+        Ok(())
+    }
+
+    /// Configure the DRDY pin as a digital output.
+    pub async fn mag_enable_int(&mut self) -> Result<(), Error<CommE, PinE>> {
+        let regc = self.dev.cfg_reg_c_m | CfgRegCM::INT_MAG;
+        self.dev.iface.write_mag_register(regc).await?;
+        self.dev.cfg_reg_c_m = regc;
+
+        Ok(())
+    }
+}
+
+impl<DI, CommE, MODE, INT1, INT2, INTMAG> Lsm303agrAsync<DI, MODE, INT1, INT2, INTMAG>
+where
+    DI: AsyncReadData<Error = Error<CommE, Infallible>>
+        + AsyncWriteData<Error = Error<CommE, Infallible>>,
+    INT1: Wait,
+{
+    /// Wait for the accelerometer data-ready interrupt on the INT1 pin.
+    ///
+    /// Returns the accelerometer status after the interrupt.
+    ///
+    /// The `DataReady1` interrupt needs to be enabled on the INT1 pin.
+    /// See: [`acc_enable_interrupt()`](Lsm303agrAsync::acc_enable_interrupt).
+    pub async fn wait_for_accel_data(&mut self) -> Result<Status, Error<CommE, INT1::Error>> {
+        wait_for_accel_int(&mut self.int1, self.dev.ctrl_reg6_a).await?;
+
+        self.dev
+            .iface
+            .read_accel_register::<StatusRegA>()
+            .await
+            .map(Status::new)
+            .map_err(with_pin_error)
+    }
+
+    /// Wait for the accelerometer FIFO watermark interrupt on the INT1 pin.
+    ///
+    /// Returns the FIFO status after the interrupt.
+    ///
+    /// The `FifoWatermark` interrupt needs to be enabled on the INT1 pin.
+    pub async fn wait_for_fifo_watermark(
+        &mut self,
+    ) -> Result<FifoStatus, Error<CommE, INT1::Error>> {
+        wait_for_accel_int(&mut self.int1, self.dev.ctrl_reg6_a).await?;
+
+        self.dev
+            .iface
+            .read_accel_register::<FifoSrcRegA>()
+            .await
+            .map(FifoStatus::new)
+            .map_err(with_pin_error)
+    }
+
+    /// Wait for the accelerometer click interrupt on the INT1 pin.
+    ///
+    /// Returns the click source after the interrupt.
+    ///
+    /// The `Click` interrupt needs to be enabled on the INT1 pin.
+    pub async fn wait_for_click(&mut self) -> Result<ClickSource, Error<CommE, INT1::Error>> {
+        wait_for_accel_int(&mut self.int1, self.dev.ctrl_reg6_a).await?;
+
+        self.dev
+            .iface
+            .read_accel_register::<ClickSrcA>()
+            .await
+            .map(ClickSource::new)
+            .map_err(with_pin_error)
+    }
+}
+
+impl<DI, CommE, MODE, INT1, INT2, INTMAG> Lsm303agrAsync<DI, MODE, INT1, INT2, INTMAG>
+where
+    DI: AsyncReadData<Error = Error<CommE, Infallible>>
+        + AsyncWriteData<Error = Error<CommE, Infallible>>,
+    INT2: Wait,
+{
+    /// Wait for the accelerometer click interrupt on the INT2 pin.
+    ///
+    /// Returns the click source after the interrupt.
+    ///
+    /// The `Click` interrupt needs to be enabled on the INT2 pin.
+    pub async fn wait_for_click_int2(&mut self) -> Result<ClickSource, Error<CommE, INT2::Error>> {
+        wait_for_accel_int(&mut self.int2, self.dev.ctrl_reg6_a).await?;
+
+        self.dev
+            .iface
+            .read_accel_register::<ClickSrcA>()
+            .await
+            .map(ClickSource::new)
+            .map_err(with_pin_error)
+    }
+}
+
+impl<DI, CommE, MODE, INT1, INT2, INTMAG> Lsm303agrAsync<DI, MODE, INT1, INT2, INTMAG>
+where
+    DI: AsyncReadData<Error = Error<CommE, Infallible>>
+        + AsyncWriteData<Error = Error<CommE, Infallible>>,
+    INTMAG: Wait,
+{
+    /// Wait for the magnetometer data-ready signal on the INT_MAG/DRDY pin.
+    ///
+    /// Returns the magnetometer status after the signal.
+    ///
+    /// The DRDY pin needs to be configured as a digital output.
+    /// See: [`mag_enable_int()`](Lsm303agrAsync::mag_enable_int).
+    pub async fn wait_for_mag_data(&mut self) -> Result<Status, Error<CommE, INTMAG::Error>> {
+        self.int_mag.wait_for_high().await.map_err(Error::Pin)?;
+
+        self.dev
+            .iface
+            .read_mag_register::<StatusRegM>()
+            .await
+            .map(Status::new)
+            .map_err(with_pin_error)
+    }
+}
+
+/// Wait for the active level of an accelerometer interrupt pin.
+async fn wait_for_accel_int<P: Wait, CommE>(
+    pin: &mut P,
+    ctrl_reg6_a: CtrlReg6A,
+) -> Result<(), Error<CommE, P::Error>> {
+    if ctrl_reg6_a.contains(CtrlReg6A::H_LACTIVE) {
+        pin.wait_for_low().await.map_err(Error::Pin)
+    } else {
+        pin.wait_for_high().await.map_err(Error::Pin)
+    }
+}
+
+/// Change the pin error type of an interface error.
+///
+/// The asynchronous interfaces have no pins, so they never return pin errors.
+fn with_pin_error<CommE, PinE>(error: Error<CommE, Infallible>) -> Error<CommE, PinE> {
+    match error {
+        Error::Comm(e) => Error::Comm(e),
+        Error::Pin(e) => match e {},
+        Error::InvalidInputData => Error::InvalidInputData,
+        Error::Timeout => Error::Timeout,
+        Error::VerifyFailed {
+            register,
+            expected,
+            actual,
+        } => Error::VerifyFailed {
+            register,
+            expected,
+            actual,
+        },
+    }
+}
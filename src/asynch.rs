@@ -0,0 +1,386 @@
+use embedded_hal_async::delay::DelayNs;
+
+use crate::{
+    interface::{AsyncReadData, AsyncWriteData, Eh1I2cInterface, Eh1SpiInterface},
+    magnetometer::{MAX_POLLS, POLL_INTERVAL_US},
+    mode,
+    register_address::{StatusRegA, StatusRegM, WhoAmIA, WhoAmIM},
+    AccelMode, AccelOutputDataRate, AccelScale, Acceleration, AccelerometerId, AxisSet, Error,
+    Lsm303agr, MagMode, MagOutputDataRate, MagneticField, MagnetometerId, ModeChangeError, Status,
+};
+
+/// LSM303AGR device driver using asynchronous I2C/SPI
+///
+/// This wraps the blocking driver's configuration state and awaits every bus
+/// transfer instead of blocking on it.
+///
+/// The INT1, INT2 and INT_MAG/DRDY pins can optionally be attached to wait
+/// for interrupts. See: [`with_int1_pin()`](Lsm303agrAsync::with_int1_pin).
+/// Waiting returns as soon as the pin is at its active level, including when
+/// it already is when starting to wait. The interrupt source therefore needs
+/// to be cleared, e.g. by reading the data, before waiting again.
+#[derive(Debug)]
+pub struct Lsm303agrAsync<DI, MODE, INT1 = (), INT2 = (), INTMAG = ()> {
+    pub(crate) dev: Lsm303agr<DI, MODE>,
+    pub(crate) int1: INT1,
+    pub(crate) int2: INT2,
+    pub(crate) int_mag: INTMAG,
+}
+
+impl<I2C> Lsm303agrAsync<Eh1I2cInterface<I2C>, mode::MagOneShot> {
+    /// Create new instance of the LSM303AGR device communicating through
+    /// asynchronous I2C.
+    pub fn new_with_i2c(i2c: I2C) -> Self {
+        Lsm303agrAsync {
+            dev: Lsm303agr::with_interface(Eh1I2cInterface { i2c, verify: false }),
+            int1: (),
+            int2: (),
+            int_mag: (),
+        }
+    }
+}
+
+impl<I2C, MODE> Lsm303agrAsync<Eh1I2cInterface<I2C>, MODE> {
+    /// Destroy driver instance, return I2C bus.
+    ///
+    /// Attached interrupt pins need to be released first.
+    /// See: [`release_pins()`](Lsm303agrAsync::release_pins).
+    pub fn destroy(self) -> I2C {
+        self.dev.destroy()
+    }
+
+    /// Enable/disable reading back every written register.
+    ///
+    /// If the value read back does not match, `Error::VerifyFailed` is returned.
+    pub fn set_verify_writes(&mut self, verify: bool) {
+        self.dev.set_verify_writes(verify)
+    }
+}
+
+impl<SPIXL, SPIMAG> Lsm303agrAsync<Eh1SpiInterface<SPIXL, SPIMAG>, mode::MagOneShot> {
+    /// Create new instance of the LSM303AGR device communicating through
+    /// asynchronous SPI devices for the accelerometer and the magnetometer.
+    ///
+    /// The magnetometer I2C interface is disabled by [`init()`](Lsm303agrAsync::init).
+    pub fn new_with_spi(spi_accel: SPIXL, spi_mag: SPIMAG) -> Self {
+        Lsm303agrAsync {
+            dev: Lsm303agr::with_interface(Eh1SpiInterface {
+                spi_xl: spi_accel,
+                spi_mag,
+                verify: false,
+            })
+            .with_mag_i2c_disabled(),
+            int1: (),
+            int2: (),
+            int_mag: (),
+        }
+    }
+
+    /// Create new instance of the LSM303AGR device communicating through
+    /// asynchronous SPI devices for the accelerometer and the magnetometer
+    /// using 3-wire SPI, where SDI and SDO share a single data line.
+    ///
+    /// The SPI bus must be configured for half-duplex (bidirectional)
+    /// operation, so that it does not drive the data line while reading.
+    ///
+    /// The accelerometer 3-wire mode and the magnetometer I2C disable bit
+    /// are written by [`init()`](Lsm303agrAsync::init). Until then, the
+    /// accelerometer does not drive the shared data line and any data read
+    /// from it is garbage, so `init()` needs to be called first.
+    pub fn new_with_spi_3_wire(spi_accel: SPIXL, spi_mag: SPIMAG) -> Self {
+        let mut driver = Self::new_with_spi(spi_accel, spi_mag);
+        driver.dev = driver.dev.with_3_wire_spi();
+        driver
+    }
+}
+
+impl<SPIXL, SPIMAG, MODE> Lsm303agrAsync<Eh1SpiInterface<SPIXL, SPIMAG>, MODE> {
+    /// Destroy driver instance, return accelerometer and magnetometer SPI devices.
+    ///
+    /// Attached interrupt pins need to be released first.
+    /// See: [`release_pins()`](Lsm303agrAsync::release_pins).
+    pub fn destroy(self) -> (SPIXL, SPIMAG) {
+        self.dev.destroy()
+    }
+
+    /// Enable/disable reading back every written register.
+    ///
+    /// If the value read back does not match, `Error::VerifyFailed` is returned.
+    pub fn set_verify_writes(&mut self, verify: bool) {
+        self.dev.set_verify_writes(verify)
+    }
+}
+
+impl<DI, MODE, INT1, INT2, INTMAG> Lsm303agrAsync<DI, MODE, INT1, INT2, INTMAG> {
+    /// Get the accelerometer mode
+    pub fn get_accel_mode(&mut self) -> AccelMode {
+        self.dev.get_accel_mode()
+    }
+
+    /// Get accelerometer scaling factor
+    pub fn get_accel_scale(&self) -> AccelScale {
+        self.dev.get_accel_scale()
+    }
+
+    /// Get enabled accelerometer axes
+    pub fn get_accel_axes(&self) -> AxisSet {
+        self.dev.get_accel_axes()
+    }
+
+    /// Get magnetometer power/resolution mode.
+    pub fn get_mag_mode(&self) -> MagMode {
+        self.dev.cfg_reg_a_m.mode()
+    }
+}
+
+impl<DI, CommE, PinE, MODE, INT1, INT2, INTMAG> Lsm303agrAsync<DI, MODE, INT1, INT2, INTMAG>
+where
+    DI: AsyncReadData<Error = Error<CommE, PinE>> + AsyncWriteData<Error = Error<CommE, PinE>>,
+{
+    /// Initialize registers
+    pub async fn init(&mut self) -> Result<(), Error<CommE, PinE>> {
+        let (reg4, temp_cfg_reg, regc, rega) = self.dev.init_registers();
+
+        self.dev.iface.write_accel_register(reg4).await?;
+        self.dev.ctrl_reg4_a = reg4;
+        self.dev.iface.write_accel_register(temp_cfg_reg).await?;
+        self.dev.temp_cfg_reg_a = temp_cfg_reg;
+        self.dev.iface.write_mag_register(regc).await?;
+        self.dev.cfg_reg_c_m = regc;
+        self.dev.iface.write_mag_register(rega).await?;
+        self.dev.cfg_reg_a_m = rega;
+
+        Ok(())
+    }
+
+    /// Set accelerometer power/resolution mode and output data rate.
+    ///
+    /// Returns `Error::InvalidInputData` if the mode is incompatible with
+    /// the given output data rate.
+    ///
+    /// The given `delay` is awaited for the time the accelerometer needs to
+    /// switch modes.
+    pub async fn set_accel_mode_and_odr<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        mode: AccelMode,
+        odr: impl Into<Option<AccelOutputDataRate>>,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let odr = odr.into();
+        let (reg1, reg4, change_time) = self.dev.accel_mode_and_odr_registers(mode, odr)?;
+
+        if mode != AccelMode::HighResolution {
+            self.dev.iface.write_accel_register(reg4).await?;
+            self.dev.ctrl_reg4_a = reg4;
+        }
+
+        self.dev.iface.write_accel_register(reg1).await?;
+        self.dev.ctrl_reg1_a = reg1;
+        self.dev.accel_odr = odr;
+
+        if mode == AccelMode::HighResolution {
+            self.dev.iface.write_accel_register(reg4).await?;
+            self.dev.ctrl_reg4_a = reg4;
+        }
+
+        if let Some(change_time) = change_time {
+            delay.delay_us(change_time).await;
+        }
+
+        Ok(())
+    }
+
+    /// Set accelerometer scaling factor
+    pub async fn set_accel_scale(&mut self, scale: AccelScale) -> Result<(), Error<CommE, PinE>> {
+        let reg4 = self.dev.ctrl_reg4_a.with_scale(scale);
+        self.dev.iface.write_accel_register(reg4).await?;
+        self.dev.ctrl_reg4_a = reg4;
+        Ok(())
+    }
+
+    /// Enable/disable accelerometer axes.
+    pub async fn set_accel_axes(&mut self, axes: AxisSet) -> Result<(), Error<CommE, PinE>> {
+        let reg1 = self.dev.ctrl_reg1_a.with_axes(axes);
+        self.dev.iface.write_accel_register(reg1).await?;
+        self.dev.ctrl_reg1_a = reg1;
+        Ok(())
+    }
+
+    /// Accelerometer status
+    pub async fn accel_status(&mut self) -> Result<Status, Error<CommE, PinE>> {
+        self.dev
+            .iface
+            .read_accel_register::<StatusRegA>()
+            .await
+            .map(Status::new)
+    }
+
+    /// Get measured acceleration.
+    pub async fn acceleration(&mut self) -> Result<Acceleration, Error<CommE, PinE>> {
+        let (x, y, z) = self
+            .dev
+            .iface
+            .read_accel_3_double_registers::<Acceleration>(self.dev.ctrl_reg4_a.endianness())
+            .await?;
+
+        Ok(Acceleration {
+            x,
+            y,
+            z,
+            mode: self.dev.get_accel_mode(),
+            scale: self.dev.get_accel_scale(),
+            axes: self.dev.get_accel_axes(),
+        })
+    }
+
+    /// Set magnetometer power/resolution mode and output data rate.
+    ///
+    /// The given `delay` is awaited for the time the magnetometer needs to
+    /// switch modes.
+    pub async fn set_mag_mode_and_odr<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        mode: MagMode,
+        odr: MagOutputDataRate,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let (rega, change_time) = self.dev.mag_mode_and_odr_register(mode, odr);
+        self.dev.iface.write_mag_register(rega).await?;
+        self.dev.cfg_reg_a_m = rega;
+
+        if let Some(change_time) = change_time {
+            delay.delay_us(change_time).await;
+        }
+
+        Ok(())
+    }
+
+    /// Magnetometer status
+    pub async fn mag_status(&mut self) -> Result<Status, Error<CommE, PinE>> {
+        self.dev
+            .iface
+            .read_mag_register::<StatusRegM>()
+            .await
+            .map(Status::new)
+    }
+
+    /// Get the accelerometer device ID.
+    pub async fn accelerometer_id(&mut self) -> Result<AccelerometerId, Error<CommE, PinE>> {
+        self.dev.iface.read_accel_register::<WhoAmIA>().await
+    }
+
+    /// Get the magnetometer device ID.
+    pub async fn magnetometer_id(&mut self) -> Result<MagnetometerId, Error<CommE, PinE>> {
+        self.dev.iface.read_mag_register::<WhoAmIM>().await
+    }
+}
+
+impl<DI, CommE, PinE, INT1, INT2, INTMAG> Lsm303agrAsync<DI, mode::MagOneShot, INT1, INT2, INTMAG>
+where
+    DI: AsyncReadData<Error = Error<CommE, PinE>> + AsyncWriteData<Error = Error<CommE, PinE>>,
+{
+    /// Take a single measurement and wait for the measured magnetic field.
+    ///
+    /// The given `delay` is awaited for the conversion time of the current
+    /// mode and output data rate. Afterwards the status is polled every
+    /// millisecond up to 10 times. If no new data is available by then,
+    /// `Error::Timeout` is returned.
+    pub async fn magnetic_field<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<MagneticField, Error<CommE, PinE>> {
+        if self.mag_status().await?.xyz_new_data() {
+            // Discard data of a previous measurement.
+            self.read_magnetic_field().await?;
+        }
+
+        let cfg = self.dev.cfg_reg_a_m.single_mode();
+        self.dev.iface.write_mag_register(cfg).await?;
+        self.dev.cfg_reg_a_m = cfg;
+
+        let offset_cancellation = self.dev.cfg_reg_b_m.offset_cancellation();
+        delay
+            .delay_us(cfg.turn_on_time_us(offset_cancellation))
+            .await;
+
+        for _ in 0..MAX_POLLS {
+            if self.mag_status().await?.xyz_new_data() {
+                return self.read_magnetic_field().await;
+            }
+            delay.delay_us(POLL_INTERVAL_US).await;
+        }
+
+        Err(Error::Timeout)
+    }
+
+    /// Change the magnetometer to continuous measurement mode
+    pub async fn into_mag_continuous(
+        mut self,
+    ) -> Result<
+        Lsm303agrAsync<DI, mode::MagContinuous, INT1, INT2, INTMAG>,
+        ModeChangeError<CommE, PinE, Self>,
+    > {
+        let cfg = self.dev.cfg_reg_a_m.continuous_mode();
+        match self.dev.iface.write_mag_register(cfg).await {
+            Err(error) => Err(ModeChangeError { error, dev: self }),
+            Ok(_) => {
+                self.dev.cfg_reg_a_m = cfg;
+                Ok(self.into_mode())
+            }
+        }
+    }
+}
+
+impl<DI, CommE, PinE, INT1, INT2, INTMAG>
+    Lsm303agrAsync<DI, mode::MagContinuous, INT1, INT2, INTMAG>
+where
+    DI: AsyncReadData<Error = Error<CommE, PinE>> + AsyncWriteData<Error = Error<CommE, PinE>>,
+{
+    /// Get the measured magnetic field.
+    pub async fn magnetic_field(&mut self) -> Result<MagneticField, Error<CommE, PinE>> {
+        self.read_magnetic_field().await
+    }
+
+    /// Change the magnetometer to one-shot mode
+    ///
+    /// After this the magnetometer is in idle mode until a one-shot measurement
+    /// is started.
+    pub async fn into_mag_one_shot(
+        mut self,
+    ) -> Result<
+        Lsm303agrAsync<DI, mode::MagOneShot, INT1, INT2, INTMAG>,
+        ModeChangeError<CommE, PinE, Self>,
+    > {
+        let cfg = self.dev.cfg_reg_a_m.idle_mode();
+        match self.dev.iface.write_mag_register(cfg).await {
+            Err(error) => Err(ModeChangeError { error, dev: self }),
+            Ok(_) => {
+                self.dev.cfg_reg_a_m = cfg;
+                Ok(self.into_mode())
+            }
+        }
+    }
+}
+
+impl<DI, CommE, PinE, MODE, INT1, INT2, INTMAG> Lsm303agrAsync<DI, MODE, INT1, INT2, INTMAG>
+where
+    DI: AsyncReadData<Error = Error<CommE, PinE>> + AsyncWriteData<Error = Error<CommE, PinE>>,
+{
+    async fn read_magnetic_field(&mut self) -> Result<MagneticField, Error<CommE, PinE>> {
+        self.dev
+            .iface
+            .read_mag_3_double_registers::<MagneticField>(self.dev.cfg_reg_c_m.endianness())
+            .await
+    }
+}
+
+impl<DI, MODE, INT1, INT2, INTMAG> Lsm303agrAsync<DI, MODE, INT1, INT2, INTMAG> {
+    /// Change the magnetometer type-state without touching the device.
+    fn into_mode<NEWMODE>(self) -> Lsm303agrAsync<DI, NEWMODE, INT1, INT2, INTMAG> {
+        Lsm303agrAsync {
+            dev: self.dev.into_mode(),
+            int1: self.int1,
+            int2: self.int2,
+            int_mag: self.int_mag,
+        }
+    }
+}
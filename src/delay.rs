@@ -0,0 +1,19 @@
+//! embedded-hal 1.0 delays
+
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal_1::delay::DelayNs;
+
+/// Adapter for passing an embedded-hal 1.0 `DelayNs` implementation to the
+/// functions which wait for the device.
+///
+/// ```ignore
+/// sensor.set_accel_mode_and_odr(&mut Eh1Delay(&mut delay), mode, odr)?;
+/// ```
+#[derive(Debug)]
+pub struct Eh1Delay<D>(pub D);
+
+impl<D: DelayNs> DelayUs<u32> for Eh1Delay<D> {
+    fn delay_us(&mut self, us: u32) {
+        self.0.delay_us(us);
+    }
+}
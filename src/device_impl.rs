@@ -1,31 +1,49 @@
+#[cfg(feature = "eh1")]
+use core::convert::Infallible;
+
+use embedded_hal::{
+    blocking::{i2c, spi},
+    digital::v2::OutputPin,
+};
+#[cfg(feature = "eh1")]
+use embedded_hal_1::spi::SpiDevice;
+
+#[cfg(feature = "eh1")]
+use crate::interface::{Eh1I2cInterface, Eh1SpiInterface};
 use crate::{
     interface::{I2cInterface, ReadData, SpiInterface, WriteData},
-    mode,
+    mode, private,
     register_address::{
-        CfgRegAM, CfgRegBM, CfgRegCM, CtrlReg1A, CtrlReg3A, CtrlReg4A, CtrlReg5A, FifoCtrlRegA,
-        StatusRegA, StatusRegAuxA, StatusRegM, TempCfgRegA, WhoAmIA, WhoAmIM,
+        CfgRegAM, CfgRegBM, CfgRegCM, CtrlReg1A, CtrlReg2A, CtrlReg3A, CtrlReg4A, CtrlReg5A,
+        CtrlReg6A, FifoCtrlRegA, FifoSrcRegA, ReferenceA, StatusRegA, StatusRegAuxA, StatusRegM,
+        TempCfgRegA, WhoAmIA, WhoAmIM,
     },
-    Acceleration, AccelerometerId, Error, FifoMode, Interrupt, Lsm303agr, MagnetometerId,
-    PhantomData, Status, Temperature, TemperatureStatus,
+    AccelMode, Acceleration, AccelerometerId, AnyMode, Endianness, Error, FifoMode, FifoStatus,
+    HighPassFilterCutoff, HighPassFilterMode, HighPassFilterTarget, Interrupt, InterruptPin,
+    InterruptPolarity, Lsm303agr, MagnetometerId, ModeChangeError, PhantomData, Status,
+    Temperature, TemperatureStatus,
 };
 
 impl<I2C> Lsm303agr<I2cInterface<I2C>, mode::MagOneShot> {
     /// Create new instance of the LSM303AGR device communicating through I2C.
     pub fn new_with_i2c(i2c: I2C) -> Self {
-        Lsm303agr {
-            iface: I2cInterface { i2c },
-            ctrl_reg1_a: CtrlReg1A::default(),
-            ctrl_reg3_a: CtrlReg3A::default(),
-            ctrl_reg4_a: CtrlReg4A::default(),
-            ctrl_reg5_a: CtrlReg5A::default(),
-            cfg_reg_a_m: CfgRegAM::default(),
-            cfg_reg_b_m: CfgRegBM::default(),
-            cfg_reg_c_m: CfgRegCM::default(),
-            temp_cfg_reg_a: TempCfgRegA::default(),
-            fifo_ctrl_reg_a: FifoCtrlRegA::default(),
-            accel_odr: None,
-            _mag_mode: PhantomData,
-        }
+        Self::with_interface(I2cInterface { i2c, verify: false })
+    }
+}
+
+impl<I2C, E> Lsm303agr<I2cInterface<I2C>, mode::MagOneShot>
+where
+    I2C: i2c::Write<Error = E> + i2c::WriteRead<Error = E>,
+{
+    /// Create new instance of the LSM303AGR device communicating through I2C,
+    /// adopting the current device configuration.
+    ///
+    /// The returned driver is in the magnetometer mode the device is in.
+    /// See [`sync_from_device()`](Lsm303agr::sync_from_device).
+    pub fn new_with_i2c_from_device(
+        i2c: I2C,
+    ) -> Result<AnyMode<I2cInterface<I2C>>, ModeChangeError<E, (), Self>> {
+        Self::new_with_i2c(i2c).into_any_mode_from_device()
     }
 }
 
@@ -34,21 +52,178 @@ impl<I2C, MODE> Lsm303agr<I2cInterface<I2C>, MODE> {
     pub fn destroy(self) -> I2C {
         self.iface.i2c
     }
+
+    /// Enable/disable reading back every written register.
+    ///
+    /// If the value read back does not match, `Error::VerifyFailed` is returned.
+    pub fn set_verify_writes(&mut self, verify: bool) {
+        self.iface.verify = verify;
+    }
 }
 
 impl<SPI, CSXL, CSMAG> Lsm303agr<SpiInterface<SPI, CSXL, CSMAG>, mode::MagOneShot> {
     /// Create new instance of the LSM303AGR device communicating through SPI.
+    ///
+    /// The magnetometer I2C interface is disabled by [`init()`](Lsm303agr::init).
     pub fn new_with_spi(spi: SPI, chip_select_accel: CSXL, chip_select_mag: CSMAG) -> Self {
+        Self::with_interface(SpiInterface {
+            spi,
+            cs_xl: chip_select_accel,
+            cs_mag: chip_select_mag,
+            verify: false,
+            three_wire: false,
+        })
+        .with_mag_i2c_disabled()
+    }
+
+    /// Create new instance of the LSM303AGR device communicating through
+    /// 3-wire SPI, where SDI and SDO share a single data line.
+    ///
+    /// The SPI peripheral must be configured for half-duplex (bidirectional)
+    /// operation, so that it does not drive the data line while reading.
+    ///
+    /// The accelerometer 3-wire mode and the magnetometer I2C disable bit
+    /// are written by [`init()`](Lsm303agr::init). Until then, the
+    /// accelerometer does not drive the shared data line and any data read
+    /// from it is garbage, so `init()` needs to be called first.
+    pub fn new_with_spi_3_wire(spi: SPI, chip_select_accel: CSXL, chip_select_mag: CSMAG) -> Self {
+        let mut dev = Self::new_with_spi(spi, chip_select_accel, chip_select_mag);
+        dev.iface.three_wire = true;
+        dev.with_3_wire_spi()
+    }
+}
+
+impl<SPI, CSXL, CSMAG, CommE, PinE, MODE> Lsm303agr<SpiInterface<SPI, CSXL, CSMAG>, MODE>
+where
+    SPI: spi::Write<u8, Error = CommE> + spi::Transfer<u8, Error = CommE>,
+    CSXL: OutputPin<Error = PinE>,
+    CSMAG: OutputPin<Error = PinE>,
+{
+    /// Disable the magnetometer I2C interface.
+    ///
+    /// This is done by [`init()`](Lsm303agr::init) when communicating through
+    /// SPI. Only a power cycle or a soft reset enables it again.
+    pub fn mag_disable_i2c(&mut self) -> Result<(), Error<CommE, PinE>> {
+        self.write_mag_i2c_disabled()
+    }
+}
+
+impl<SPI, CSXL, CSMAG, CommE, PinE> Lsm303agr<SpiInterface<SPI, CSXL, CSMAG>, mode::MagOneShot>
+where
+    SPI: spi::Write<u8, Error = CommE> + spi::Transfer<u8, Error = CommE>,
+    CSXL: OutputPin<Error = PinE>,
+    CSMAG: OutputPin<Error = PinE>,
+{
+    /// Create new instance of the LSM303AGR device communicating through SPI,
+    /// adopting the current device configuration.
+    ///
+    /// The returned driver is in the magnetometer mode the device is in.
+    /// See [`sync_from_device()`](Lsm303agr::sync_from_device).
+    #[allow(clippy::type_complexity)]
+    pub fn new_with_spi_from_device(
+        spi: SPI,
+        chip_select_accel: CSXL,
+        chip_select_mag: CSMAG,
+    ) -> Result<AnyMode<SpiInterface<SPI, CSXL, CSMAG>>, ModeChangeError<CommE, PinE, Self>> {
+        Self::new_with_spi(spi, chip_select_accel, chip_select_mag).into_any_mode_from_device()
+    }
+}
+
+#[cfg(feature = "eh1")]
+impl<I2C> Lsm303agr<Eh1I2cInterface<I2C>, mode::MagOneShot> {
+    /// Create new instance of the LSM303AGR device communicating through an
+    /// embedded-hal 1.0 I2C bus.
+    pub fn new_with_eh1_i2c(i2c: I2C) -> Self {
+        Self::with_interface(Eh1I2cInterface { i2c, verify: false })
+    }
+}
+
+#[cfg(feature = "eh1")]
+impl<I2C, MODE> Lsm303agr<Eh1I2cInterface<I2C>, MODE> {
+    /// Destroy driver instance, return I2C bus.
+    pub fn destroy(self) -> I2C {
+        self.iface.i2c
+    }
+
+    /// Enable/disable reading back every written register.
+    ///
+    /// If the value read back does not match, `Error::VerifyFailed` is returned.
+    pub fn set_verify_writes(&mut self, verify: bool) {
+        self.iface.verify = verify;
+    }
+}
+
+#[cfg(feature = "eh1")]
+impl<SPIXL, SPIMAG> Lsm303agr<Eh1SpiInterface<SPIXL, SPIMAG>, mode::MagOneShot> {
+    /// Create new instance of the LSM303AGR device communicating through
+    /// embedded-hal 1.0 SPI devices for the accelerometer and the magnetometer.
+    ///
+    /// The magnetometer I2C interface is disabled by [`init()`](Lsm303agr::init).
+    pub fn new_with_eh1_spi(spi_accel: SPIXL, spi_mag: SPIMAG) -> Self {
+        Self::with_interface(Eh1SpiInterface {
+            spi_xl: spi_accel,
+            spi_mag,
+            verify: false,
+        })
+        .with_mag_i2c_disabled()
+    }
+
+    /// Create new instance of the LSM303AGR device communicating through
+    /// embedded-hal 1.0 SPI devices for the accelerometer and the magnetometer
+    /// using 3-wire SPI, where SDI and SDO share a single data line.
+    ///
+    /// The SPI bus must be configured for half-duplex (bidirectional)
+    /// operation, so that it does not drive the data line while reading.
+    ///
+    /// The accelerometer 3-wire mode and the magnetometer I2C disable bit
+    /// are written by [`init()`](Lsm303agr::init). Until then, the
+    /// accelerometer does not drive the shared data line and any data read
+    /// from it is garbage, so `init()` needs to be called first.
+    pub fn new_with_eh1_spi_3_wire(spi_accel: SPIXL, spi_mag: SPIMAG) -> Self {
+        Self::new_with_eh1_spi(spi_accel, spi_mag).with_3_wire_spi()
+    }
+}
+
+#[cfg(feature = "eh1")]
+impl<SPIXL, SPIMAG, MODE> Lsm303agr<Eh1SpiInterface<SPIXL, SPIMAG>, MODE> {
+    /// Destroy driver instance, return accelerometer and magnetometer SPI devices.
+    pub fn destroy(self) -> (SPIXL, SPIMAG) {
+        (self.iface.spi_xl, self.iface.spi_mag)
+    }
+
+    /// Enable/disable reading back every written register.
+    ///
+    /// If the value read back does not match, `Error::VerifyFailed` is returned.
+    pub fn set_verify_writes(&mut self, verify: bool) {
+        self.iface.verify = verify;
+    }
+}
+
+#[cfg(feature = "eh1")]
+impl<SPIXL, SPIMAG, E, MODE> Lsm303agr<Eh1SpiInterface<SPIXL, SPIMAG>, MODE>
+where
+    SPIXL: SpiDevice<Error = E>,
+    SPIMAG: SpiDevice<Error = E>,
+{
+    /// Disable the magnetometer I2C interface.
+    ///
+    /// This is done by [`init()`](Lsm303agr::init) when communicating through
+    /// SPI. Only a power cycle or a soft reset enables it again.
+    pub fn mag_disable_i2c(&mut self) -> Result<(), Error<E, Infallible>> {
+        self.write_mag_i2c_disabled()
+    }
+}
+
+impl<DI> Lsm303agr<DI, mode::MagOneShot> {
+    pub(crate) fn with_interface(iface: DI) -> Self {
         Lsm303agr {
-            iface: SpiInterface {
-                spi,
-                cs_xl: chip_select_accel,
-                cs_mag: chip_select_mag,
-            },
+            iface,
             ctrl_reg1_a: CtrlReg1A::default(),
+            ctrl_reg2_a: CtrlReg2A::default(),
             ctrl_reg3_a: CtrlReg3A::default(),
             ctrl_reg4_a: CtrlReg4A::default(),
             ctrl_reg5_a: CtrlReg5A::default(),
+            ctrl_reg6_a: CtrlReg6A::default(),
             cfg_reg_a_m: CfgRegAM::default(),
             cfg_reg_b_m: CfgRegBM::default(),
             cfg_reg_c_m: CfgRegCM::default(),
@@ -58,6 +233,37 @@ impl<SPI, CSXL, CSMAG> Lsm303agr<SpiInterface<SPI, CSXL, CSMAG>, mode::MagOneSho
             _mag_mode: PhantomData,
         }
     }
+
+    /// Disable the magnetometer I2C interface once the configuration is written.
+    pub(crate) fn with_mag_i2c_disabled(mut self) -> Self {
+        self.cfg_reg_c_m |= CfgRegCM::I2C_DIS;
+        self
+    }
+
+    /// Select 3-wire SPI for the accelerometer once the configuration is written.
+    pub(crate) fn with_3_wire_spi(mut self) -> Self {
+        self.ctrl_reg4_a |= CtrlReg4A::SPI_ENABLE;
+        self
+    }
+}
+
+impl<DI, CommE, PinE> Lsm303agr<DI, mode::MagOneShot>
+where
+    DI: ReadData<Error = Error<CommE, PinE>> + WriteData<Error = Error<CommE, PinE>>,
+{
+    fn into_any_mode_from_device(
+        mut self,
+    ) -> Result<AnyMode<DI>, ModeChangeError<CommE, PinE, Self>> {
+        if let Err(error) = self.sync_from_device() {
+            return Err(ModeChangeError { error, dev: self });
+        }
+
+        Ok(if self.cfg_reg_a_m.is_continuous_mode() {
+            AnyMode::MagContinuous(self.into_mode())
+        } else {
+            AnyMode::MagOneShot(self.into_mode())
+        })
+    }
 }
 
 impl<SPI, CSXL, CSMAG, MODE> Lsm303agr<SpiInterface<SPI, CSXL, CSMAG>, MODE> {
@@ -65,6 +271,33 @@ impl<SPI, CSXL, CSMAG, MODE> Lsm303agr<SpiInterface<SPI, CSXL, CSMAG>, MODE> {
     pub fn destroy(self) -> (SPI, CSXL, CSMAG) {
         (self.iface.spi, self.iface.cs_xl, self.iface.cs_mag)
     }
+
+    /// Enable/disable reading back every written register.
+    ///
+    /// If the value read back does not match, `Error::VerifyFailed` is returned.
+    pub fn set_verify_writes(&mut self, verify: bool) {
+        self.iface.verify = verify;
+    }
+}
+
+impl<DI: private::Sealed, MODE> Lsm303agr<DI, MODE> {
+    /// Registers written by `init()`, in order: block data update for the
+    /// accelerometer and the temperature sensor, block data update for the
+    /// magnetometer, disabling its I2C interface when using SPI, and
+    /// magnetometer temperature compensation.
+    pub(crate) fn init_registers(&self) -> (CtrlReg4A, TempCfgRegA, CfgRegCM, CfgRegAM) {
+        let mut regc = self.cfg_reg_c_m | CfgRegCM::BDU;
+        if DI::SPI {
+            regc |= CfgRegCM::I2C_DIS;
+        }
+
+        (
+            self.ctrl_reg4_a | CtrlReg4A::BDU,
+            self.temp_cfg_reg_a | TempCfgRegA::TEMP_EN,
+            regc,
+            self.cfg_reg_a_m | CfgRegAM::COMP_TEMP_EN,
+        )
+    }
 }
 
 impl<DI, CommE, PinE, MODE> Lsm303agr<DI, MODE>
@@ -73,38 +306,44 @@ where
 {
     /// Initialize registers
     pub fn init(&mut self) -> Result<(), Error<CommE, PinE>> {
-        self.acc_enable_temp()?; // Also enables BDU.
-        self.mag_enable_bdu()
-    }
+        let (reg4, temp_cfg_reg, regc, rega) = self.init_registers();
 
-    /// Enable block data update for accelerometer.
-    #[inline]
-    fn acc_enable_bdu(&mut self) -> Result<(), Error<CommE, PinE>> {
-        let reg4 = self.ctrl_reg4_a | CtrlReg4A::BDU;
         self.iface.write_accel_register(reg4)?;
         self.ctrl_reg4_a = reg4;
-
-        Ok(())
-    }
-
-    /// Enable the temperature sensor.
-    #[inline]
-    fn acc_enable_temp(&mut self) -> Result<(), Error<CommE, PinE>> {
-        self.acc_enable_bdu()?;
-
-        let temp_cfg_reg = self.temp_cfg_reg_a | TempCfgRegA::TEMP_EN;
         self.iface.write_accel_register(temp_cfg_reg)?;
         self.temp_cfg_reg_a = temp_cfg_reg;
+        self.iface.write_mag_register(regc)?;
+        self.cfg_reg_c_m = regc;
+        self.iface.write_mag_register(rega)?;
+        self.cfg_reg_a_m = rega;
 
         Ok(())
     }
 
-    /// Enable block data update for magnetometer.
-    #[inline]
-    fn mag_enable_bdu(&mut self) -> Result<(), Error<CommE, PinE>> {
-        let regc = self.cfg_reg_c_m | CfgRegCM::BDU;
-        self.iface.write_mag_register(regc)?;
-        self.cfg_reg_c_m = regc;
+    /// Read the control registers from the device into the driver.
+    ///
+    /// This is needed if the device configuration was changed without using
+    /// this driver instance, e.g. after a microcontroller reset while the
+    /// device stayed powered.
+    ///
+    /// The magnetometer type-state is not changed. To get the driver in the
+    /// magnetometer mode the device is in, use
+    /// [`new_with_i2c_from_device()`](Lsm303agr::new_with_i2c_from_device) or
+    /// [`new_with_spi_from_device()`](Lsm303agr::new_with_spi_from_device).
+    pub fn sync_from_device(&mut self) -> Result<(), Error<CommE, PinE>> {
+        self.ctrl_reg1_a = self.iface.read_accel_register::<CtrlReg1A>()?;
+        self.ctrl_reg2_a = self.iface.read_accel_register::<CtrlReg2A>()?;
+        self.ctrl_reg3_a = self.iface.read_accel_register::<CtrlReg3A>()?;
+        self.ctrl_reg4_a = self.iface.read_accel_register::<CtrlReg4A>()?;
+        self.ctrl_reg5_a = self.iface.read_accel_register::<CtrlReg5A>()?;
+        self.ctrl_reg6_a = self.iface.read_accel_register::<CtrlReg6A>()?;
+        self.temp_cfg_reg_a = self.iface.read_accel_register::<TempCfgRegA>()?;
+        self.fifo_ctrl_reg_a = self.iface.read_accel_register::<FifoCtrlRegA>()?;
+        self.accel_odr = self.ctrl_reg1_a.odr();
+
+        self.cfg_reg_a_m = self.iface.read_mag_register::<CfgRegAM>()?;
+        self.cfg_reg_b_m = self.iface.read_mag_register::<CfgRegBM>()?;
+        self.cfg_reg_c_m = self.iface.read_mag_register::<CfgRegCM>()?;
 
         Ok(())
     }
@@ -128,7 +367,49 @@ where
         Ok(())
     }
 
-    /// Enable accelerometer interrupt.
+    /// Get the accelerometer FIFO status.
+    pub fn acc_fifo_status(&mut self) -> Result<FifoStatus, Error<CommE, PinE>> {
+        self.iface
+            .read_accel_register::<FifoSrcRegA>()
+            .map(FifoStatus::new)
+    }
+
+    /// Read the unread samples stored in the accelerometer FIFO.
+    ///
+    /// All samples are read in a single burst, up to the length of `data`.
+    /// Returns the number of samples read.
+    pub fn acc_read_fifo(
+        &mut self,
+        data: &mut [Acceleration],
+    ) -> Result<usize, Error<CommE, PinE>> {
+        let len = usize::from(self.acc_fifo_status()?.len()).min(data.len());
+
+        let mut raw = [(0, 0, 0); FifoSrcRegA::CAPACITY as usize];
+        let raw = &mut raw[..len];
+        self.iface
+            .read_accel_3_double_registers_burst::<Acceleration>(
+                self.ctrl_reg4_a.endianness(),
+                raw,
+            )?;
+
+        let mode = self.get_accel_mode();
+        let scale = self.get_accel_scale();
+        let axes = self.get_accel_axes();
+        for (acceleration, &(x, y, z)) in data.iter_mut().zip(raw.iter()) {
+            *acceleration = Acceleration {
+                x,
+                y,
+                z,
+                mode,
+                scale,
+                axes,
+            };
+        }
+
+        Ok(len)
+    }
+
+    /// Enable accelerometer interrupt on the INT1 pin.
     pub fn acc_enable_interrupt(&mut self, interrupt: Interrupt) -> Result<(), Error<CommE, PinE>> {
         let reg3 = self.ctrl_reg3_a.with_interrupt(interrupt);
         self.iface.write_accel_register(reg3)?;
@@ -137,7 +418,7 @@ where
         Ok(())
     }
 
-    /// Disable accelerometer interrupt.
+    /// Disable accelerometer interrupt on the INT1 pin.
     pub fn acc_disable_interrupt(
         &mut self,
         interrupt: Interrupt,
@@ -149,6 +430,105 @@ where
         Ok(())
     }
 
+    /// Enable accelerometer interrupt on the given pin.
+    ///
+    /// Only [`Interrupt::Click`], [`Interrupt::Aoi1`] and [`Interrupt::Aoi2`]
+    /// can be routed to the INT2 pin. Returns `Error::InvalidInputData` for
+    /// any other interrupt on INT2.
+    pub fn acc_enable_interrupt_on(
+        &mut self,
+        pin: InterruptPin,
+        interrupt: Interrupt,
+    ) -> Result<(), Error<CommE, PinE>> {
+        match pin {
+            InterruptPin::Int1 => self.acc_enable_interrupt(interrupt),
+            InterruptPin::Int2 => {
+                let reg6 = self
+                    .ctrl_reg6_a
+                    .with_interrupt(interrupt)
+                    .ok_or(Error::InvalidInputData)?;
+                self.iface.write_accel_register(reg6)?;
+                self.ctrl_reg6_a = reg6;
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Disable accelerometer interrupt on the given pin.
+    ///
+    /// Returns `Error::InvalidInputData` for interrupts which cannot be routed
+    /// to the INT2 pin.
+    pub fn acc_disable_interrupt_on(
+        &mut self,
+        pin: InterruptPin,
+        interrupt: Interrupt,
+    ) -> Result<(), Error<CommE, PinE>> {
+        match pin {
+            InterruptPin::Int1 => self.acc_disable_interrupt(interrupt),
+            InterruptPin::Int2 => {
+                let reg6 = self
+                    .ctrl_reg6_a
+                    .without_interrupt(interrupt)
+                    .ok_or(Error::InvalidInputData)?;
+                self.iface.write_accel_register(reg6)?;
+                self.ctrl_reg6_a = reg6;
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Set the polarity of the accelerometer interrupt pins.
+    pub fn acc_set_interrupt_polarity(
+        &mut self,
+        polarity: InterruptPolarity,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let reg6 = self.ctrl_reg6_a.with_polarity(polarity);
+        self.iface.write_accel_register(reg6)?;
+        self.ctrl_reg6_a = reg6;
+
+        Ok(())
+    }
+
+    fn write_mag_i2c_disabled(&mut self) -> Result<(), Error<CommE, PinE>> {
+        let regc = self.cfg_reg_c_m | CfgRegCM::I2C_DIS;
+        self.iface.write_mag_register(regc)?;
+        self.cfg_reg_c_m = regc;
+
+        Ok(())
+    }
+
+    /// Set the byte order of the accelerometer output data registers.
+    ///
+    /// Big-endian output is only available in high-resolution mode.
+    /// The data read by the driver is converted accordingly.
+    ///
+    /// Returns `Error::InvalidInputData` if big-endian output is requested
+    /// while the accelerometer is not in high-resolution mode.
+    pub fn acc_set_endianness(&mut self, endianness: Endianness) -> Result<(), Error<CommE, PinE>> {
+        if endianness == Endianness::Big && self.get_accel_mode() != AccelMode::HighResolution {
+            return Err(Error::InvalidInputData);
+        }
+
+        let reg4 = self.ctrl_reg4_a.with_endianness(endianness);
+        self.iface.write_accel_register(reg4)?;
+        self.ctrl_reg4_a = reg4;
+
+        Ok(())
+    }
+
+    /// Set the byte order of the magnetometer output data registers.
+    ///
+    /// The data read by the driver is converted accordingly.
+    pub fn mag_set_endianness(&mut self, endianness: Endianness) -> Result<(), Error<CommE, PinE>> {
+        let regc = self.cfg_reg_c_m.with_endianness(endianness);
+        self.iface.write_mag_register(regc)?;
+        self.cfg_reg_c_m = regc;
+
+        Ok(())
+    }
+
     /// Configure the DRDY pin as a digital output.
     pub fn mag_enable_int(&mut self) -> Result<(), Error<CommE, PinE>> {
         let regc = self.cfg_reg_c_m | CfgRegCM::INT_MAG;
@@ -158,6 +538,28 @@ where
         Ok(())
     }
 
+    /// Enable magnetometer temperature compensation.
+    ///
+    /// This is needed for correct operation of the magnetometer and is done
+    /// by [`init()`](Lsm303agr::init). It is kept when changing the mode or
+    /// output data rate.
+    pub fn mag_enable_temp_compensation(&mut self) -> Result<(), Error<CommE, PinE>> {
+        let rega = self.cfg_reg_a_m.union(CfgRegAM::COMP_TEMP_EN);
+        self.iface.write_mag_register(rega)?;
+        self.cfg_reg_a_m = rega;
+
+        Ok(())
+    }
+
+    /// Disable magnetometer temperature compensation.
+    pub fn mag_disable_temp_compensation(&mut self) -> Result<(), Error<CommE, PinE>> {
+        let rega = self.cfg_reg_a_m.difference(CfgRegAM::COMP_TEMP_EN);
+        self.iface.write_mag_register(rega)?;
+        self.cfg_reg_a_m = rega;
+
+        Ok(())
+    }
+
     /// Enable magnetometer low-pass filter.
     pub fn mag_enable_low_pass_filter(&mut self) -> Result<(), Error<CommE, PinE>> {
         let regb = self.cfg_reg_b_m.union(CfgRegBM::LPF);
@@ -176,6 +578,56 @@ where
         Ok(())
     }
 
+    /// Set the accelerometer high-pass filter mode and cut-off frequency.
+    ///
+    /// The filter is only applied to the targets enabled with
+    /// [`acc_enable_high_pass_filter()`](Lsm303agr::acc_enable_high_pass_filter).
+    pub fn acc_set_high_pass_filter(
+        &mut self,
+        mode: HighPassFilterMode,
+        cutoff: HighPassFilterCutoff,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let reg2 = self.ctrl_reg2_a.with_hp_mode(mode).with_hp_cutoff(cutoff);
+        self.iface.write_accel_register(reg2)?;
+        self.ctrl_reg2_a = reg2;
+
+        Ok(())
+    }
+
+    /// Apply the accelerometer high-pass filter to the given target.
+    pub fn acc_enable_high_pass_filter(
+        &mut self,
+        target: HighPassFilterTarget,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let reg2 = self.ctrl_reg2_a.with_hp_target(target);
+        self.iface.write_accel_register(reg2)?;
+        self.ctrl_reg2_a = reg2;
+
+        Ok(())
+    }
+
+    /// Bypass the accelerometer high-pass filter for the given target.
+    pub fn acc_disable_high_pass_filter(
+        &mut self,
+        target: HighPassFilterTarget,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let reg2 = self.ctrl_reg2_a.without_hp_target(target);
+        self.iface.write_accel_register(reg2)?;
+        self.ctrl_reg2_a = reg2;
+
+        Ok(())
+    }
+
+    /// Reset the accelerometer high-pass filter to the current acceleration.
+    ///
+    /// This reads the REFERENCE register, which zeroes the filter output
+    /// instantly in [`HighPassFilterMode::NormalWithReset`] mode.
+    pub fn acc_reset_hp_filter_reference(&mut self) -> Result<(), Error<CommE, PinE>> {
+        self.iface.read_accel_register::<ReferenceA>()?;
+
+        Ok(())
+    }
+
     /// Accelerometer status
     pub fn accel_status(&mut self) -> Result<Status, Error<CommE, PinE>> {
         self.iface
@@ -185,7 +637,9 @@ where
 
     /// Get measured acceleration.
     pub fn acceleration(&mut self) -> Result<Acceleration, Error<CommE, PinE>> {
-        let (x, y, z) = self.iface.read_accel_3_double_registers::<Acceleration>()?;
+        let (x, y, z) = self
+            .iface
+            .read_accel_3_double_registers::<Acceleration>(self.ctrl_reg4_a.endianness())?;
 
         Ok(Acceleration {
             x,
@@ -193,6 +647,7 @@ where
             z,
             mode: self.get_accel_mode(),
             scale: self.get_accel_scale(),
+            axes: self.get_accel_axes(),
         })
     }
 
@@ -215,7 +670,8 @@ where
 
     /// Get measured temperature.
     pub fn temperature(&mut self) -> Result<Temperature, Error<CommE, PinE>> {
-        self.iface.read_accel_double_register::<Temperature>()
+        self.iface
+            .read_accel_double_register::<Temperature>(self.ctrl_reg4_a.endianness())
     }
 
     /// Temperature sensor status
@@ -7,25 +7,45 @@ use embedded_hal::{
 
 use crate::{
     private,
-    register_address::{RegRead, RegWrite},
-    Error,
+    register_address::{CfgRegAM, CtrlReg5A, FifoSrcRegA, RegRead, RegWrite},
+    Endianness, Error,
 };
 
+#[cfg(feature = "async")]
+mod asynch;
+#[cfg(feature = "async")]
+pub use asynch::{AsyncReadData, AsyncWriteData};
+#[cfg(feature = "eh1")]
+mod eh1;
+#[cfg(feature = "eh1")]
+pub use eh1::{Eh1I2cInterface, Eh1SpiInterface};
+
 pub(crate) const ACCEL_ADDR: u8 = 0b001_1001;
 pub(crate) const MAG_ADDR: u8 = 0b001_1110;
 
+const SPI_RW: u8 = 1 << 7;
+const SPI_MS: u8 = 1 << 6;
+
 /// I2C interface
 #[derive(Debug)]
 pub struct I2cInterface<I2C> {
     pub(crate) i2c: I2C,
+    pub(crate) verify: bool,
 }
 
 /// SPI interface
+///
+/// In 3-wire mode, reads write the register address and then read the data
+/// in a separate transfer, so the SPI peripheral can switch the direction of
+/// the shared SDI/SDO line in between. This requires the SPI peripheral to be
+/// in half-duplex (bidirectional) mode.
 #[derive(Debug)]
 pub struct SpiInterface<SPI, CSXL, CSMAG> {
     pub(crate) spi: SPI,
     pub(crate) cs_xl: CSXL,
     pub(crate) cs_mag: CSMAG,
+    pub(crate) verify: bool,
+    pub(crate) three_wire: bool,
 }
 
 /// Write data
@@ -36,28 +56,49 @@ pub trait WriteData: private::Sealed {
     fn write_accel_register<R: RegWrite>(&mut self, reg: R) -> Result<(), Self::Error>;
     /// Write to an u8 magnetometer register
     fn write_mag_register<R: RegWrite>(&mut self, reg: R) -> Result<(), Self::Error>;
+    /// Write to 3 u16 magnetometer registers
+    fn write_mag_3_double_registers<R: RegWrite<(u16, u16, u16)>>(
+        &mut self,
+        reg: R,
+    ) -> Result<(), Self::Error>;
 }
 
 impl<I2C, E> WriteData for I2cInterface<I2C>
 where
-    I2C: i2c::Write<Error = E>,
+    I2C: i2c::Write<Error = E> + i2c::WriteRead<Error = E>,
 {
     type Error = Error<E, ()>;
 
     fn write_accel_register<R: RegWrite>(&mut self, reg: R) -> Result<(), Self::Error> {
-        let payload: [u8; 2] = [R::ADDR, reg.data()];
-        self.i2c.write(ACCEL_ADDR, &payload).map_err(Error::Comm)
+        self.write_register(ACCEL_ADDR, reg)
     }
 
     fn write_mag_register<R: RegWrite>(&mut self, reg: R) -> Result<(), Self::Error> {
-        let payload: [u8; 2] = [R::ADDR, reg.data()];
-        self.i2c.write(MAG_ADDR, &payload).map_err(Error::Comm)
+        self.write_register(MAG_ADDR, reg)
+    }
+
+    fn write_mag_3_double_registers<R: RegWrite<(u16, u16, u16)>>(
+        &mut self,
+        reg: R,
+    ) -> Result<(), Self::Error> {
+        let payload = encode_3_double_registers(R::ADDR | 0x80, reg);
+        self.i2c.write(MAG_ADDR, &payload).map_err(Error::Comm)?;
+
+        if self.verify {
+            let mut actual = [0; 6];
+            self.i2c
+                .write_read(MAG_ADDR, &[R::ADDR | 0x80], &mut actual)
+                .map_err(Error::Comm)?;
+            verify_3_double_registers::<R, _, _>(&payload[1..], &actual)?;
+        }
+
+        Ok(())
     }
 }
 
 impl<SPI, CSXL, CSMAG, CommE, PinE> WriteData for SpiInterface<SPI, CSXL, CSMAG>
 where
-    SPI: spi::Write<u8, Error = CommE>,
+    SPI: spi::Write<u8, Error = CommE> + spi::Transfer<u8, Error = CommE>,
     CSXL: OutputPin<Error = PinE>,
     CSMAG: OutputPin<Error = PinE>,
 {
@@ -71,7 +112,16 @@ where
         let result = self.spi.write(&payload).map_err(Error::Comm);
 
         self.cs_xl.set_high().map_err(Error::Pin)?;
-        result
+        result?;
+
+        if self.verify && is_verifiable::<R>(reg.data()) {
+            self.cs_xl.set_low().map_err(Error::Pin)?;
+            let actual = self.read_byte(R::ADDR);
+            self.cs_xl.set_high().map_err(Error::Pin)?;
+            verify_register::<R, _, _>(reg.data(), actual?)?;
+        }
+
+        Ok(())
     }
 
     fn write_mag_register<R: RegWrite>(&mut self, reg: R) -> Result<(), Self::Error> {
@@ -82,7 +132,39 @@ where
         let result = self.spi.write(&payload).map_err(Error::Comm);
 
         self.cs_mag.set_high().map_err(Error::Pin)?;
-        result
+        result?;
+
+        if self.verify && is_verifiable::<R>(reg.data()) {
+            self.cs_mag.set_low().map_err(Error::Pin)?;
+            let actual = self.read_byte(R::ADDR);
+            self.cs_mag.set_high().map_err(Error::Pin)?;
+            verify_register::<R, _, _>(reg.data(), actual?)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_mag_3_double_registers<R: RegWrite<(u16, u16, u16)>>(
+        &mut self,
+        reg: R,
+    ) -> Result<(), Self::Error> {
+        self.cs_mag.set_low().map_err(Error::Pin)?;
+
+        let payload = encode_3_double_registers(SPI_MS | R::ADDR, reg);
+        let result = self.spi.write(&payload).map_err(Error::Comm);
+
+        self.cs_mag.set_high().map_err(Error::Pin)?;
+        result?;
+
+        if self.verify {
+            let mut buffer = [0; 7];
+            self.cs_mag.set_low().map_err(Error::Pin)?;
+            let result = self.read(SPI_MS | R::ADDR, &mut buffer);
+            self.cs_mag.set_high().map_err(Error::Pin)?;
+            verify_3_double_registers::<R, _, _>(&payload[1..], result?)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -94,16 +176,30 @@ pub trait ReadData: private::Sealed {
     fn read_accel_register<R: RegRead>(&mut self) -> Result<R::Output, Self::Error>;
     /// Read an u8 magnetometer register
     fn read_mag_register<R: RegRead>(&mut self) -> Result<R::Output, Self::Error>;
-    /// Read an u16 accelerometer register
-    fn read_accel_double_register<R: RegRead<u16>>(&mut self) -> Result<R::Output, Self::Error>;
-    /// Read 3 u16 accelerometer registers
+    /// Read an u16 accelerometer register with the given byte order
+    fn read_accel_double_register<R: RegRead<u16>>(
+        &mut self,
+        endianness: Endianness,
+    ) -> Result<R::Output, Self::Error>;
+    /// Read 3 u16 accelerometer registers with the given byte order
     fn read_accel_3_double_registers<R: RegRead<(u16, u16, u16)>>(
         &mut self,
+        endianness: Endianness,
     ) -> Result<R::Output, Self::Error>;
+    /// Read 3 u16 accelerometer registers repeatedly in a single burst
+    ///
+    /// This relies on the address pointer rolling back to the first register,
+    /// which is the case when reading from the FIFO.
+    fn read_accel_3_double_registers_burst<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+        endianness: Endianness,
+        data: &mut [R::Output],
+    ) -> Result<(), Self::Error>;
 
-    /// Read 3 u16 magnetometer registers
+    /// Read 3 u16 magnetometer registers with the given byte order
     fn read_mag_3_double_registers<R: RegRead<(u16, u16, u16)>>(
         &mut self,
+        endianness: Endianness,
     ) -> Result<R::Output, Self::Error>;
 }
 
@@ -121,20 +217,61 @@ where
         self.read_register::<R>(MAG_ADDR)
     }
 
-    fn read_accel_double_register<R: RegRead<u16>>(&mut self) -> Result<R::Output, Self::Error> {
-        self.read_double_register::<R>(ACCEL_ADDR)
+    fn read_accel_double_register<R: RegRead<u16>>(
+        &mut self,
+        endianness: Endianness,
+    ) -> Result<R::Output, Self::Error> {
+        self.read_double_register::<R>(ACCEL_ADDR, endianness)
     }
 
     fn read_accel_3_double_registers<R: RegRead<(u16, u16, u16)>>(
         &mut self,
+        endianness: Endianness,
     ) -> Result<R::Output, Self::Error> {
-        self.read_3_double_registers::<R>(ACCEL_ADDR)
+        self.read_3_double_registers::<R>(ACCEL_ADDR, endianness)
+    }
+
+    fn read_accel_3_double_registers_burst<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+        endianness: Endianness,
+        data: &mut [R::Output],
+    ) -> Result<(), Self::Error> {
+        let mut buffer = [0; 6 * FifoSrcRegA::CAPACITY as usize];
+
+        for chunk in data.chunks_mut(FifoSrcRegA::CAPACITY as usize) {
+            let bytes = &mut buffer[..chunk.len() * 6];
+            self.i2c
+                .write_read(ACCEL_ADDR, &[R::ADDR | 0x80], bytes)
+                .map_err(Error::Comm)?;
+
+            decode_3_double_registers_burst::<R>(bytes, endianness, chunk);
+        }
+
+        Ok(())
     }
 
     fn read_mag_3_double_registers<R: RegRead<(u16, u16, u16)>>(
         &mut self,
+        endianness: Endianness,
     ) -> Result<R::Output, Self::Error> {
-        self.read_3_double_registers::<R>(MAG_ADDR)
+        self.read_3_double_registers::<R>(MAG_ADDR, endianness)
+    }
+}
+
+impl<I2C, E> I2cInterface<I2C>
+where
+    I2C: i2c::Write<Error = E> + i2c::WriteRead<Error = E>,
+{
+    fn write_register<R: RegWrite>(&mut self, address: u8, reg: R) -> Result<(), Error<E, ()>> {
+        let payload: [u8; 2] = [R::ADDR, reg.data()];
+        self.i2c.write(address, &payload).map_err(Error::Comm)?;
+
+        if self.verify && is_verifiable::<R>(reg.data()) {
+            let actual = self.read_byte(address, R::ADDR)?;
+            verify_register::<R, _, _>(reg.data(), actual)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -142,47 +279,49 @@ impl<I2C, E> I2cInterface<I2C>
 where
     I2C: i2c::WriteRead<Error = E>,
 {
-    fn read_register<R: RegRead>(&mut self, address: u8) -> Result<R::Output, Error<E, ()>> {
+    fn read_byte(&mut self, address: u8, register: u8) -> Result<u8, Error<E, ()>> {
         let mut data = [0];
         self.i2c
-            .write_read(address, &[R::ADDR], &mut data)
+            .write_read(address, &[register], &mut data)
             .map_err(Error::Comm)?;
 
-        Ok(R::from_data(data[0]))
+        Ok(data[0])
+    }
+
+    fn read_register<R: RegRead>(&mut self, address: u8) -> Result<R::Output, Error<E, ()>> {
+        self.read_byte(address, R::ADDR).map(R::from_data)
     }
 
     fn read_double_register<R: RegRead<u16>>(
         &mut self,
         address: u8,
+        endianness: Endianness,
     ) -> Result<R::Output, Error<E, ()>> {
         let mut data = [0; 2];
         self.i2c
             .write_read(address, &[R::ADDR | 0x80], &mut data)
             .map_err(Error::Comm)?;
 
-        Ok(R::from_data(u16::from_le_bytes(data)))
+        Ok(R::from_data(decode_double_register(data, endianness)))
     }
 
     fn read_3_double_registers<R: RegRead<(u16, u16, u16)>>(
         &mut self,
         address: u8,
+        endianness: Endianness,
     ) -> Result<R::Output, Error<E, ()>> {
         let mut data = [0; 6];
         self.i2c
             .write_read(address, &[R::ADDR | 0x80], &mut data)
             .map_err(Error::Comm)?;
 
-        Ok(R::from_data((
-            u16::from_le_bytes([data[0], data[1]]),
-            u16::from_le_bytes([data[2], data[3]]),
-            u16::from_le_bytes([data[4], data[5]]),
-        )))
+        Ok(R::from_data(decode_3_double_registers(&data, endianness)))
     }
 }
 
 impl<SPI, CSXL, CSMAG, CommE, PinE> ReadData for SpiInterface<SPI, CSXL, CSMAG>
 where
-    SPI: spi::Transfer<u8, Error = CommE>,
+    SPI: spi::Write<u8, Error = CommE> + spi::Transfer<u8, Error = CommE>,
     CSXL: OutputPin<Error = PinE>,
     CSMAG: OutputPin<Error = PinE>,
 {
@@ -202,27 +341,47 @@ where
         result
     }
 
-    fn read_accel_double_register<R: RegRead<u16>>(&mut self) -> Result<R::Output, Self::Error> {
+    fn read_accel_double_register<R: RegRead<u16>>(
+        &mut self,
+        endianness: Endianness,
+    ) -> Result<R::Output, Self::Error> {
         self.cs_xl.set_low().map_err(Error::Pin)?;
-        let result = self.read_double_register::<R>();
+        let result = self.read_double_register::<R>(endianness);
         self.cs_xl.set_high().map_err(Error::Pin)?;
         result
     }
 
     fn read_accel_3_double_registers<R: RegRead<(u16, u16, u16)>>(
         &mut self,
+        endianness: Endianness,
     ) -> Result<R::Output, Self::Error> {
         self.cs_xl.set_low().map_err(Error::Pin)?;
-        let result = self.read_3_double_registers::<R>();
+        let result = self.read_3_double_registers::<R>(endianness);
         self.cs_xl.set_high().map_err(Error::Pin)?;
         result
     }
 
+    fn read_accel_3_double_registers_burst<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+        endianness: Endianness,
+        data: &mut [R::Output],
+    ) -> Result<(), Self::Error> {
+        for chunk in data.chunks_mut(FifoSrcRegA::CAPACITY as usize) {
+            self.cs_xl.set_low().map_err(Error::Pin)?;
+            let result = self.read_3_double_registers_burst::<R>(endianness, chunk);
+            self.cs_xl.set_high().map_err(Error::Pin)?;
+            result?;
+        }
+
+        Ok(())
+    }
+
     fn read_mag_3_double_registers<R: RegRead<(u16, u16, u16)>>(
         &mut self,
+        endianness: Endianness,
     ) -> Result<R::Output, Self::Error> {
         self.cs_mag.set_low().map_err(Error::Pin)?;
-        let result = self.read_3_double_registers::<R>();
+        let result = self.read_3_double_registers::<R>(endianness);
         self.cs_mag.set_high().map_err(Error::Pin)?;
         result
     }
@@ -230,37 +389,168 @@ where
 
 impl<SPI, CSXL, CSMAG, CommE, PinE> SpiInterface<SPI, CSXL, CSMAG>
 where
-    SPI: spi::Transfer<u8, Error = CommE>,
+    SPI: spi::Write<u8, Error = CommE> + spi::Transfer<u8, Error = CommE>,
     CSXL: OutputPin<Error = PinE>,
     CSMAG: OutputPin<Error = PinE>,
 {
-    const SPI_RW: u8 = 1 << 7;
-    const SPI_MS: u8 = 1 << 6;
+    /// Read `buffer.len() - 1` bytes, the first byte of `buffer` is used for
+    /// the address.
+    fn read<'b>(
+        &mut self,
+        address: u8,
+        buffer: &'b mut [u8],
+    ) -> Result<&'b [u8], Error<CommE, PinE>> {
+        if self.three_wire {
+            // The address and the data are separate transfers, so that a
+            // half-duplex SPI peripheral can release the shared data line to
+            // the device in between. The bytes clocked out while reading must
+            // not be driven onto the line.
+            self.spi.write(&[SPI_RW | address]).map_err(Error::Comm)?;
+            self.spi.transfer(&mut buffer[1..]).map_err(Error::Comm)
+        } else {
+            buffer[0] = SPI_RW | address;
+            let bytes = self.spi.transfer(buffer).map_err(Error::Comm)?;
+            Ok(&bytes[1..])
+        }
+    }
 
-    fn read_register<R: RegRead>(&mut self) -> Result<R::Output, Error<CommE, PinE>> {
-        let mut data = [Self::SPI_RW | R::ADDR, 0];
-        self.spi.transfer(&mut data).map_err(Error::Comm)?;
+    fn read_byte(&mut self, register: u8) -> Result<u8, Error<CommE, PinE>> {
+        let mut buffer = [0; 2];
+        let data = self.read(register, &mut buffer)?;
 
-        Ok(R::from_data(data[1]))
+        Ok(data[0])
     }
 
-    fn read_double_register<R: RegRead<u16>>(&mut self) -> Result<R::Output, Error<CommE, PinE>> {
-        let mut data = [Self::SPI_RW | Self::SPI_MS | R::ADDR, 0, 0];
-        self.spi.transfer(&mut data).map_err(Error::Comm)?;
+    fn read_register<R: RegRead>(&mut self) -> Result<R::Output, Error<CommE, PinE>> {
+        self.read_byte(R::ADDR).map(R::from_data)
+    }
 
-        Ok(R::from_data(u16::from_le_bytes([data[1], data[2]])))
+    fn read_double_register<R: RegRead<u16>>(
+        &mut self,
+        endianness: Endianness,
+    ) -> Result<R::Output, Error<CommE, PinE>> {
+        let mut buffer = [0; 3];
+        let data = self.read(SPI_MS | R::ADDR, &mut buffer)?;
+
+        Ok(R::from_data(decode_double_register(
+            [data[0], data[1]],
+            endianness,
+        )))
     }
 
     fn read_3_double_registers<R: RegRead<(u16, u16, u16)>>(
         &mut self,
+        endianness: Endianness,
     ) -> Result<R::Output, Error<CommE, PinE>> {
-        let mut data = [Self::SPI_RW | Self::SPI_MS | R::ADDR, 0, 0, 0, 0, 0, 0];
-        self.spi.transfer(&mut data).map_err(Error::Comm)?;
+        let mut buffer = [0; 7];
+        let data = self.read(SPI_MS | R::ADDR, &mut buffer)?;
 
-        Ok(R::from_data((
-            u16::from_le_bytes([data[1], data[2]]),
-            u16::from_le_bytes([data[3], data[4]]),
-            u16::from_le_bytes([data[5], data[6]]),
-        )))
+        Ok(R::from_data(decode_3_double_registers(data, endianness)))
     }
+
+    fn read_3_double_registers_burst<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+        endianness: Endianness,
+        data: &mut [R::Output],
+    ) -> Result<(), Error<CommE, PinE>> {
+        let mut buffer = [0; 1 + 6 * FifoSrcRegA::CAPACITY as usize];
+        let bytes = self.read(SPI_MS | R::ADDR, &mut buffer[..1 + data.len() * 6])?;
+
+        decode_3_double_registers_burst::<R>(bytes, endianness, data);
+
+        Ok(())
+    }
+}
+
+/// Whether a written register value can be read back.
+///
+/// A magnetometer soft reset restores the default register values, so the
+/// written value is not kept.
+fn is_verifiable<R: RegWrite>(value: u8) -> bool {
+    !(R::ADDR == <CfgRegAM as RegRead>::ADDR
+        && CfgRegAM::from_bits_truncate(value).contains(CfgRegAM::SOFT_RST))
+}
+
+/// Compare a written register value with the value read back, ignoring bits
+/// which are cleared by the device after being written.
+///
+/// In single mode, the magnetometer returns to idle mode once the measurement
+/// is done, so the mode bits are ignored as well.
+fn verify_register<R: RegWrite, CommE, PinE>(
+    expected: u8,
+    actual: u8,
+) -> Result<(), Error<CommE, PinE>> {
+    let self_clearing = if R::ADDR == <CtrlReg5A as RegRead>::ADDR {
+        CtrlReg5A::BOOT.bits()
+    } else if R::ADDR == <CfgRegAM as RegRead>::ADDR {
+        let mut bits = CfgRegAM::REBOOT;
+        if CfgRegAM::from_bits_truncate(expected).is_single_mode() {
+            bits |= CfgRegAM::MD;
+        }
+        bits.bits()
+    } else {
+        0
+    };
+
+    if (expected ^ actual) & !self_clearing == 0 {
+        Ok(())
+    } else {
+        Err(Error::VerifyFailed {
+            register: R::ADDR,
+            expected,
+            actual,
+        })
+    }
+}
+
+/// Compare the written bytes of 3 u16 registers with the bytes read back.
+fn verify_3_double_registers<R: RegWrite<(u16, u16, u16)>, CommE, PinE>(
+    expected: &[u8],
+    actual: &[u8],
+) -> Result<(), Error<CommE, PinE>> {
+    match expected.iter().zip(actual).position(|(e, a)| e != a) {
+        None => Ok(()),
+        Some(i) => Err(Error::VerifyFailed {
+            register: R::ADDR + i as u8,
+            expected: expected[i],
+            actual: actual[i],
+        }),
+    }
+}
+
+/// Decode an u16 register with the configured byte order of the output data.
+fn decode_double_register(data: [u8; 2], endianness: Endianness) -> u16 {
+    match endianness {
+        Endianness::Little => u16::from_le_bytes(data),
+        Endianness::Big => u16::from_be_bytes(data),
+    }
+}
+
+fn decode_3_double_registers(data: &[u8], endianness: Endianness) -> (u16, u16, u16) {
+    (
+        decode_double_register([data[0], data[1]], endianness),
+        decode_double_register([data[2], data[3]], endianness),
+        decode_double_register([data[4], data[5]], endianness),
+    )
+}
+
+/// Decode 3 u16 registers read repeatedly in a single burst.
+fn decode_3_double_registers_burst<R: RegRead<(u16, u16, u16)>>(
+    bytes: &[u8],
+    endianness: Endianness,
+    data: &mut [R::Output],
+) {
+    for (output, bytes) in data.iter_mut().zip(bytes.chunks(6)) {
+        *output = R::from_data(decode_3_double_registers(bytes, endianness));
+    }
+}
+
+/// Encode 3 u16 registers after the given address byte for a single write.
+fn encode_3_double_registers<R: RegWrite<(u16, u16, u16)>>(address: u8, reg: R) -> [u8; 7] {
+    let (x, y, z) = reg.data();
+    let [x_l, x_h] = x.to_le_bytes();
+    let [y_l, y_h] = y.to_le_bytes();
+    let [z_l, z_h] = z.to_le_bytes();
+
+    [address, x_l, x_h, y_l, y_h, z_l, z_h]
 }
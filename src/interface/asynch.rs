@@ -0,0 +1,363 @@
+//! Asynchronous I2C/SPI interfaces
+
+use core::convert::Infallible;
+
+use embedded_hal_1::spi::Operation;
+use embedded_hal_async::{i2c::I2c, spi::SpiDevice};
+
+use super::{
+    decode_3_double_registers, decode_3_double_registers_burst, decode_double_register,
+    encode_3_double_registers, is_verifiable, verify_3_double_registers, verify_register,
+    Eh1I2cInterface, Eh1SpiInterface, ACCEL_ADDR, MAG_ADDR, SPI_MS, SPI_RW,
+};
+use crate::{
+    private,
+    register_address::{FifoSrcRegA, RegRead, RegWrite},
+    Endianness, Error,
+};
+
+/// Write data asynchronously
+#[allow(async_fn_in_trait)]
+pub trait AsyncWriteData: private::Sealed {
+    /// Error type
+    type Error;
+    /// Write to an u8 accelerometer register
+    async fn write_accel_register<R: RegWrite>(&mut self, reg: R) -> Result<(), Self::Error>;
+    /// Write to an u8 magnetometer register
+    async fn write_mag_register<R: RegWrite>(&mut self, reg: R) -> Result<(), Self::Error>;
+    /// Write to 3 u16 magnetometer registers
+    async fn write_mag_3_double_registers<R: RegWrite<(u16, u16, u16)>>(
+        &mut self,
+        reg: R,
+    ) -> Result<(), Self::Error>;
+}
+
+/// Read data asynchronously
+#[allow(async_fn_in_trait)]
+pub trait AsyncReadData: private::Sealed {
+    /// Error type
+    type Error;
+    /// Read an u8 accelerometer register
+    async fn read_accel_register<R: RegRead>(&mut self) -> Result<R::Output, Self::Error>;
+    /// Read an u8 magnetometer register
+    async fn read_mag_register<R: RegRead>(&mut self) -> Result<R::Output, Self::Error>;
+    /// Read an u16 accelerometer register with the given byte order
+    async fn read_accel_double_register<R: RegRead<u16>>(
+        &mut self,
+        endianness: Endianness,
+    ) -> Result<R::Output, Self::Error>;
+    /// Read 3 u16 accelerometer registers with the given byte order
+    async fn read_accel_3_double_registers<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+        endianness: Endianness,
+    ) -> Result<R::Output, Self::Error>;
+    /// Read 3 u16 accelerometer registers repeatedly in a single burst
+    ///
+    /// This relies on the address pointer rolling back to the first register,
+    /// which is the case when reading from the FIFO.
+    async fn read_accel_3_double_registers_burst<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+        endianness: Endianness,
+        data: &mut [R::Output],
+    ) -> Result<(), Self::Error>;
+    /// Read 3 u16 magnetometer registers with the given byte order
+    async fn read_mag_3_double_registers<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+        endianness: Endianness,
+    ) -> Result<R::Output, Self::Error>;
+}
+
+impl<I2C, E> AsyncWriteData for Eh1I2cInterface<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    type Error = Error<E, Infallible>;
+
+    async fn write_accel_register<R: RegWrite>(&mut self, reg: R) -> Result<(), Self::Error> {
+        self.write_register_async(ACCEL_ADDR, reg).await
+    }
+
+    async fn write_mag_register<R: RegWrite>(&mut self, reg: R) -> Result<(), Self::Error> {
+        self.write_register_async(MAG_ADDR, reg).await
+    }
+
+    async fn write_mag_3_double_registers<R: RegWrite<(u16, u16, u16)>>(
+        &mut self,
+        reg: R,
+    ) -> Result<(), Self::Error> {
+        let payload = encode_3_double_registers(R::ADDR | 0x80, reg);
+        self.i2c
+            .write(MAG_ADDR, &payload)
+            .await
+            .map_err(Error::Comm)?;
+
+        if self.verify {
+            let mut actual = [0; 6];
+            self.i2c
+                .write_read(MAG_ADDR, &[R::ADDR | 0x80], &mut actual)
+                .await
+                .map_err(Error::Comm)?;
+            verify_3_double_registers::<R, _, _>(&payload[1..], &actual)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<I2C, E> AsyncReadData for Eh1I2cInterface<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    type Error = Error<E, Infallible>;
+
+    async fn read_accel_register<R: RegRead>(&mut self) -> Result<R::Output, Self::Error> {
+        self.read_byte_async(ACCEL_ADDR, R::ADDR)
+            .await
+            .map(R::from_data)
+    }
+
+    async fn read_mag_register<R: RegRead>(&mut self) -> Result<R::Output, Self::Error> {
+        self.read_byte_async(MAG_ADDR, R::ADDR)
+            .await
+            .map(R::from_data)
+    }
+
+    async fn read_accel_double_register<R: RegRead<u16>>(
+        &mut self,
+        endianness: Endianness,
+    ) -> Result<R::Output, Self::Error> {
+        let mut data = [0; 2];
+        self.i2c
+            .write_read(ACCEL_ADDR, &[R::ADDR | 0x80], &mut data)
+            .await
+            .map_err(Error::Comm)?;
+
+        Ok(R::from_data(decode_double_register(data, endianness)))
+    }
+
+    async fn read_accel_3_double_registers<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+        endianness: Endianness,
+    ) -> Result<R::Output, Self::Error> {
+        self.read_3_double_registers_async::<R>(ACCEL_ADDR, endianness)
+            .await
+    }
+
+    async fn read_accel_3_double_registers_burst<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+        endianness: Endianness,
+        data: &mut [R::Output],
+    ) -> Result<(), Self::Error> {
+        let mut buffer = [0; 6 * FifoSrcRegA::CAPACITY as usize];
+
+        for chunk in data.chunks_mut(FifoSrcRegA::CAPACITY as usize) {
+            let bytes = &mut buffer[..chunk.len() * 6];
+            self.i2c
+                .write_read(ACCEL_ADDR, &[R::ADDR | 0x80], bytes)
+                .await
+                .map_err(Error::Comm)?;
+
+            decode_3_double_registers_burst::<R>(bytes, endianness, chunk);
+        }
+
+        Ok(())
+    }
+
+    async fn read_mag_3_double_registers<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+        endianness: Endianness,
+    ) -> Result<R::Output, Self::Error> {
+        self.read_3_double_registers_async::<R>(MAG_ADDR, endianness)
+            .await
+    }
+}
+
+impl<I2C, E> Eh1I2cInterface<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    async fn write_register_async<R: RegWrite>(
+        &mut self,
+        address: u8,
+        reg: R,
+    ) -> Result<(), Error<E, Infallible>> {
+        let payload: [u8; 2] = [R::ADDR, reg.data()];
+        self.i2c
+            .write(address, &payload)
+            .await
+            .map_err(Error::Comm)?;
+
+        if self.verify && is_verifiable::<R>(reg.data()) {
+            let actual = self.read_byte_async(address, R::ADDR).await?;
+            verify_register::<R, _, _>(reg.data(), actual)?;
+        }
+
+        Ok(())
+    }
+
+    async fn read_byte_async(
+        &mut self,
+        address: u8,
+        register: u8,
+    ) -> Result<u8, Error<E, Infallible>> {
+        let mut data = [0];
+        self.i2c
+            .write_read(address, &[register], &mut data)
+            .await
+            .map_err(Error::Comm)?;
+
+        Ok(data[0])
+    }
+
+    async fn read_3_double_registers_async<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+        address: u8,
+        endianness: Endianness,
+    ) -> Result<R::Output, Error<E, Infallible>> {
+        let mut data = [0; 6];
+        self.i2c
+            .write_read(address, &[R::ADDR | 0x80], &mut data)
+            .await
+            .map_err(Error::Comm)?;
+
+        Ok(R::from_data(decode_3_double_registers(&data, endianness)))
+    }
+}
+
+impl<SPIXL, SPIMAG, E> AsyncWriteData for Eh1SpiInterface<SPIXL, SPIMAG>
+where
+    SPIXL: SpiDevice<Error = E>,
+    SPIMAG: SpiDevice<Error = E>,
+{
+    type Error = Error<E, Infallible>;
+
+    async fn write_accel_register<R: RegWrite>(&mut self, reg: R) -> Result<(), Self::Error> {
+        write_register(&mut self.spi_xl, reg, self.verify).await
+    }
+
+    async fn write_mag_register<R: RegWrite>(&mut self, reg: R) -> Result<(), Self::Error> {
+        write_register(&mut self.spi_mag, reg, self.verify).await
+    }
+
+    async fn write_mag_3_double_registers<R: RegWrite<(u16, u16, u16)>>(
+        &mut self,
+        reg: R,
+    ) -> Result<(), Self::Error> {
+        let payload = encode_3_double_registers(SPI_MS | R::ADDR, reg);
+        self.spi_mag.write(&payload).await.map_err(Error::Comm)?;
+
+        if self.verify {
+            let mut actual = [0; 6];
+            read(&mut self.spi_mag, SPI_MS | R::ADDR, &mut actual).await?;
+            verify_3_double_registers::<R, _, _>(&payload[1..], &actual)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<SPIXL, SPIMAG, E> AsyncReadData for Eh1SpiInterface<SPIXL, SPIMAG>
+where
+    SPIXL: SpiDevice<Error = E>,
+    SPIMAG: SpiDevice<Error = E>,
+{
+    type Error = Error<E, Infallible>;
+
+    async fn read_accel_register<R: RegRead>(&mut self) -> Result<R::Output, Self::Error> {
+        read_byte(&mut self.spi_xl, R::ADDR).await.map(R::from_data)
+    }
+
+    async fn read_mag_register<R: RegRead>(&mut self) -> Result<R::Output, Self::Error> {
+        read_byte(&mut self.spi_mag, R::ADDR)
+            .await
+            .map(R::from_data)
+    }
+
+    async fn read_accel_double_register<R: RegRead<u16>>(
+        &mut self,
+        endianness: Endianness,
+    ) -> Result<R::Output, Self::Error> {
+        let mut data = [0; 2];
+        read(&mut self.spi_xl, SPI_MS | R::ADDR, &mut data).await?;
+
+        Ok(R::from_data(decode_double_register(data, endianness)))
+    }
+
+    async fn read_accel_3_double_registers<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+        endianness: Endianness,
+    ) -> Result<R::Output, Self::Error> {
+        read_3_double_registers::<R, _>(&mut self.spi_xl, endianness).await
+    }
+
+    async fn read_accel_3_double_registers_burst<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+        endianness: Endianness,
+        data: &mut [R::Output],
+    ) -> Result<(), Self::Error> {
+        let mut buffer = [0; 6 * FifoSrcRegA::CAPACITY as usize];
+
+        for chunk in data.chunks_mut(FifoSrcRegA::CAPACITY as usize) {
+            let bytes = &mut buffer[..chunk.len() * 6];
+            read(&mut self.spi_xl, SPI_MS | R::ADDR, bytes).await?;
+
+            decode_3_double_registers_burst::<R>(bytes, endianness, chunk);
+        }
+
+        Ok(())
+    }
+
+    async fn read_mag_3_double_registers<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+        endianness: Endianness,
+    ) -> Result<R::Output, Self::Error> {
+        read_3_double_registers::<R, _>(&mut self.spi_mag, endianness).await
+    }
+}
+
+async fn write_register<R: RegWrite, SPI: SpiDevice>(
+    spi: &mut SPI,
+    reg: R,
+    verify: bool,
+) -> Result<(), Error<SPI::Error, Infallible>> {
+    // note that multiple byte writing needs to set the MS bit
+    let payload: [u8; 2] = [R::ADDR, reg.data()];
+    spi.write(&payload).await.map_err(Error::Comm)?;
+
+    if verify && is_verifiable::<R>(reg.data()) {
+        let actual = read_byte(spi, R::ADDR).await?;
+        verify_register::<R, _, _>(reg.data(), actual)?;
+    }
+
+    Ok(())
+}
+
+async fn read<SPI: SpiDevice>(
+    spi: &mut SPI,
+    address: u8,
+    data: &mut [u8],
+) -> Result<(), Error<SPI::Error, Infallible>> {
+    spi.transaction(&mut [Operation::Write(&[SPI_RW | address]), Operation::Read(data)])
+        .await
+        .map_err(Error::Comm)
+}
+
+async fn read_byte<SPI: SpiDevice>(
+    spi: &mut SPI,
+    register: u8,
+) -> Result<u8, Error<SPI::Error, Infallible>> {
+    let mut data = [0];
+    read(spi, register, &mut data).await?;
+
+    Ok(data[0])
+}
+
+async fn read_3_double_registers<R: RegRead<(u16, u16, u16)>, SPI: SpiDevice>(
+    spi: &mut SPI,
+    endianness: Endianness,
+) -> Result<R::Output, Error<SPI::Error, Infallible>> {
+    let mut data = [0; 6];
+    read(spi, SPI_MS | R::ADDR, &mut data).await?;
+
+    Ok(R::from_data(decode_3_double_registers(&data, endianness)))
+}
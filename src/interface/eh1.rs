@@ -0,0 +1,319 @@
+//! embedded-hal 1.0 I2C/SPI interfaces
+
+use core::convert::Infallible;
+
+use embedded_hal_1::{
+    i2c::I2c,
+    spi::{Operation, SpiDevice},
+};
+
+use super::{
+    decode_3_double_registers, decode_3_double_registers_burst, decode_double_register,
+    encode_3_double_registers, is_verifiable, verify_3_double_registers, verify_register, ReadData,
+    WriteData, ACCEL_ADDR, MAG_ADDR, SPI_MS, SPI_RW,
+};
+use crate::{
+    register_address::{FifoSrcRegA, RegRead, RegWrite},
+    Endianness, Error,
+};
+
+/// embedded-hal 1.0 I2C interface
+///
+/// There are no pins to manage, so the pin error type is `Infallible`.
+///
+/// With the `async` feature, this is also the interface for asynchronous I2C.
+#[derive(Debug)]
+pub struct Eh1I2cInterface<I2C> {
+    pub(crate) i2c: I2C,
+    pub(crate) verify: bool,
+}
+
+/// embedded-hal 1.0 SPI interface
+///
+/// The accelerometer and the magnetometer are separate SPI devices, each
+/// managing its own chip select.
+///
+/// Reads write the register address and read the data in separate
+/// operations, so 3-wire SPI buses are supported as well.
+///
+/// The chip selects are managed by the `SpiDevice`s, so the pin error type
+/// is `Infallible`.
+///
+/// With the `async` feature, this is also the interface for asynchronous SPI.
+#[derive(Debug)]
+pub struct Eh1SpiInterface<SPIXL, SPIMAG> {
+    pub(crate) spi_xl: SPIXL,
+    pub(crate) spi_mag: SPIMAG,
+    pub(crate) verify: bool,
+}
+
+impl<I2C, E> WriteData for Eh1I2cInterface<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    type Error = Error<E, Infallible>;
+
+    fn write_accel_register<R: RegWrite>(&mut self, reg: R) -> Result<(), Self::Error> {
+        self.write_register(ACCEL_ADDR, reg)
+    }
+
+    fn write_mag_register<R: RegWrite>(&mut self, reg: R) -> Result<(), Self::Error> {
+        self.write_register(MAG_ADDR, reg)
+    }
+
+    fn write_mag_3_double_registers<R: RegWrite<(u16, u16, u16)>>(
+        &mut self,
+        reg: R,
+    ) -> Result<(), Self::Error> {
+        let payload = encode_3_double_registers(R::ADDR | 0x80, reg);
+        self.i2c.write(MAG_ADDR, &payload).map_err(Error::Comm)?;
+
+        if self.verify {
+            let mut actual = [0; 6];
+            self.i2c
+                .write_read(MAG_ADDR, &[R::ADDR | 0x80], &mut actual)
+                .map_err(Error::Comm)?;
+            verify_3_double_registers::<R, _, _>(&payload[1..], &actual)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<I2C, E> ReadData for Eh1I2cInterface<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    type Error = Error<E, Infallible>;
+
+    fn read_accel_register<R: RegRead>(&mut self) -> Result<R::Output, Self::Error> {
+        self.read_byte(ACCEL_ADDR, R::ADDR).map(R::from_data)
+    }
+
+    fn read_mag_register<R: RegRead>(&mut self) -> Result<R::Output, Self::Error> {
+        self.read_byte(MAG_ADDR, R::ADDR).map(R::from_data)
+    }
+
+    fn read_accel_double_register<R: RegRead<u16>>(
+        &mut self,
+        endianness: Endianness,
+    ) -> Result<R::Output, Self::Error> {
+        let mut data = [0; 2];
+        self.i2c
+            .write_read(ACCEL_ADDR, &[R::ADDR | 0x80], &mut data)
+            .map_err(Error::Comm)?;
+
+        Ok(R::from_data(decode_double_register(data, endianness)))
+    }
+
+    fn read_accel_3_double_registers<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+        endianness: Endianness,
+    ) -> Result<R::Output, Self::Error> {
+        self.read_3_double_registers::<R>(ACCEL_ADDR, endianness)
+    }
+
+    fn read_accel_3_double_registers_burst<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+        endianness: Endianness,
+        data: &mut [R::Output],
+    ) -> Result<(), Self::Error> {
+        let mut buffer = [0; 6 * FifoSrcRegA::CAPACITY as usize];
+
+        for chunk in data.chunks_mut(FifoSrcRegA::CAPACITY as usize) {
+            let bytes = &mut buffer[..chunk.len() * 6];
+            self.i2c
+                .write_read(ACCEL_ADDR, &[R::ADDR | 0x80], bytes)
+                .map_err(Error::Comm)?;
+
+            decode_3_double_registers_burst::<R>(bytes, endianness, chunk);
+        }
+
+        Ok(())
+    }
+
+    fn read_mag_3_double_registers<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+        endianness: Endianness,
+    ) -> Result<R::Output, Self::Error> {
+        self.read_3_double_registers::<R>(MAG_ADDR, endianness)
+    }
+}
+
+impl<I2C, E> Eh1I2cInterface<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    fn write_register<R: RegWrite>(
+        &mut self,
+        address: u8,
+        reg: R,
+    ) -> Result<(), Error<E, Infallible>> {
+        let payload: [u8; 2] = [R::ADDR, reg.data()];
+        self.i2c.write(address, &payload).map_err(Error::Comm)?;
+
+        if self.verify && is_verifiable::<R>(reg.data()) {
+            let actual = self.read_byte(address, R::ADDR)?;
+            verify_register::<R, _, _>(reg.data(), actual)?;
+        }
+
+        Ok(())
+    }
+
+    fn read_byte(&mut self, address: u8, register: u8) -> Result<u8, Error<E, Infallible>> {
+        let mut data = [0];
+        self.i2c
+            .write_read(address, &[register], &mut data)
+            .map_err(Error::Comm)?;
+
+        Ok(data[0])
+    }
+
+    fn read_3_double_registers<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+        address: u8,
+        endianness: Endianness,
+    ) -> Result<R::Output, Error<E, Infallible>> {
+        let mut data = [0; 6];
+        self.i2c
+            .write_read(address, &[R::ADDR | 0x80], &mut data)
+            .map_err(Error::Comm)?;
+
+        Ok(R::from_data(decode_3_double_registers(&data, endianness)))
+    }
+}
+
+impl<SPIXL, SPIMAG, E> WriteData for Eh1SpiInterface<SPIXL, SPIMAG>
+where
+    SPIXL: SpiDevice<Error = E>,
+    SPIMAG: SpiDevice<Error = E>,
+{
+    type Error = Error<E, Infallible>;
+
+    fn write_accel_register<R: RegWrite>(&mut self, reg: R) -> Result<(), Self::Error> {
+        write_register(&mut self.spi_xl, reg, self.verify)
+    }
+
+    fn write_mag_register<R: RegWrite>(&mut self, reg: R) -> Result<(), Self::Error> {
+        write_register(&mut self.spi_mag, reg, self.verify)
+    }
+
+    fn write_mag_3_double_registers<R: RegWrite<(u16, u16, u16)>>(
+        &mut self,
+        reg: R,
+    ) -> Result<(), Self::Error> {
+        let payload = encode_3_double_registers(SPI_MS | R::ADDR, reg);
+        self.spi_mag.write(&payload).map_err(Error::Comm)?;
+
+        if self.verify {
+            let mut actual = [0; 6];
+            read(&mut self.spi_mag, SPI_MS | R::ADDR, &mut actual)?;
+            verify_3_double_registers::<R, _, _>(&payload[1..], &actual)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<SPIXL, SPIMAG, E> ReadData for Eh1SpiInterface<SPIXL, SPIMAG>
+where
+    SPIXL: SpiDevice<Error = E>,
+    SPIMAG: SpiDevice<Error = E>,
+{
+    type Error = Error<E, Infallible>;
+
+    fn read_accel_register<R: RegRead>(&mut self) -> Result<R::Output, Self::Error> {
+        read_byte(&mut self.spi_xl, R::ADDR).map(R::from_data)
+    }
+
+    fn read_mag_register<R: RegRead>(&mut self) -> Result<R::Output, Self::Error> {
+        read_byte(&mut self.spi_mag, R::ADDR).map(R::from_data)
+    }
+
+    fn read_accel_double_register<R: RegRead<u16>>(
+        &mut self,
+        endianness: Endianness,
+    ) -> Result<R::Output, Self::Error> {
+        let mut data = [0; 2];
+        read(&mut self.spi_xl, SPI_MS | R::ADDR, &mut data)?;
+
+        Ok(R::from_data(decode_double_register(data, endianness)))
+    }
+
+    fn read_accel_3_double_registers<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+        endianness: Endianness,
+    ) -> Result<R::Output, Self::Error> {
+        read_3_double_registers::<R, _>(&mut self.spi_xl, endianness)
+    }
+
+    fn read_accel_3_double_registers_burst<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+        endianness: Endianness,
+        data: &mut [R::Output],
+    ) -> Result<(), Self::Error> {
+        let mut buffer = [0; 6 * FifoSrcRegA::CAPACITY as usize];
+
+        for chunk in data.chunks_mut(FifoSrcRegA::CAPACITY as usize) {
+            let bytes = &mut buffer[..chunk.len() * 6];
+            read(&mut self.spi_xl, SPI_MS | R::ADDR, bytes)?;
+
+            decode_3_double_registers_burst::<R>(bytes, endianness, chunk);
+        }
+
+        Ok(())
+    }
+
+    fn read_mag_3_double_registers<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+        endianness: Endianness,
+    ) -> Result<R::Output, Self::Error> {
+        read_3_double_registers::<R, _>(&mut self.spi_mag, endianness)
+    }
+}
+
+fn write_register<R: RegWrite, SPI: SpiDevice>(
+    spi: &mut SPI,
+    reg: R,
+    verify: bool,
+) -> Result<(), Error<SPI::Error, Infallible>> {
+    // note that multiple byte writing needs to set the MS bit
+    let payload: [u8; 2] = [R::ADDR, reg.data()];
+    spi.write(&payload).map_err(Error::Comm)?;
+
+    if verify && is_verifiable::<R>(reg.data()) {
+        let actual = read_byte(spi, R::ADDR)?;
+        verify_register::<R, _, _>(reg.data(), actual)?;
+    }
+
+    Ok(())
+}
+
+fn read<SPI: SpiDevice>(
+    spi: &mut SPI,
+    address: u8,
+    data: &mut [u8],
+) -> Result<(), Error<SPI::Error, Infallible>> {
+    spi.transaction(&mut [Operation::Write(&[SPI_RW | address]), Operation::Read(data)])
+        .map_err(Error::Comm)
+}
+
+fn read_byte<SPI: SpiDevice>(
+    spi: &mut SPI,
+    register: u8,
+) -> Result<u8, Error<SPI::Error, Infallible>> {
+    let mut data = [0];
+    read(spi, register, &mut data)?;
+
+    Ok(data[0])
+}
+
+fn read_3_double_registers<R: RegRead<(u16, u16, u16)>, SPI: SpiDevice>(
+    spi: &mut SPI,
+    endianness: Endianness,
+) -> Result<R::Output, Error<SPI::Error, Infallible>> {
+    let mut data = [0; 6];
+    read(spi, SPI_MS | R::ADDR, &mut data)?;
+
+    Ok(R::from_data(decode_3_double_registers(&data, endianness)))
+}
@@ -6,25 +6,59 @@
 //!
 //! This driver allows you to:
 //! - Connect through I2C or SPI. See: [`new_with_i2c()`](Lsm303agr::new_with_i2c) and [`new_with_spi()`](Lsm303agr::new_with_spi) .
+//! - Connect through 3-wire SPI. See: [`new_with_spi_3_wire()`](Lsm303agr::new_with_spi_3_wire).
+//! - Connect through embedded-hal 1.0 I2C or SPI devices (`eh1` feature). See: `new_with_eh1_i2c()` and `new_with_eh1_spi()`.
+//! - Use the device asynchronously (`async` feature). See: `Lsm303agrAsync`.
+//! - Await the INT1, INT2 and INT_MAG/DRDY pins (`async` feature). See: `wait_for_accel_data()`, `wait_for_fifo_watermark()`, `wait_for_click()` and `wait_for_mag_data()`.
 //! - Initialize the device. See: [`init()`](Lsm303agr::init).
+//! - Reset the device. See: [`reset()`](Lsm303agr::reset).
+//! - Verify register writes by reading them back. See: [`set_verify_writes()`](Lsm303agr::set_verify_writes).
+//! - Read the configuration from the device. See: [`sync_from_device()`](Lsm303agr::sync_from_device), [`new_with_i2c_from_device()`](Lsm303agr::new_with_i2c_from_device) and [`new_with_spi_from_device()`](Lsm303agr::new_with_spi_from_device).
 //! - Accelerometer:
 //!     - Read measured acceleration. See: [`acceleration()`](Lsm303agr::acceleration).
 //!     - Get accelerometer status. See: [`accel_status()`](Lsm303agr::accel_status).
 //!     - Set accelerometer mode and output data rate. See: [`set_accel_mode_and_odr()`](Lsm303agr::set_accel_mode_and_odr).
 //!     - Set accelerometer scale. See: [`set_accel_scale()`](Lsm303agr::set_accel_scale).
+//!     - Enable/disable accelerometer axes. See: [`set_accel_axes()`](Lsm303agr::set_accel_axes).
+//!     - Select big-endian accelerometer output. See: [`acc_set_endianness()`](Lsm303agr::acc_set_endianness).
 //!     - Get accelerometer ID. See: [`accelerometer_id()`](Lsm303agr::accelerometer_id).
 //!     - Get temperature sensor status. See: [`temperature_status()`](Lsm303agr::temperature_status).
 //!     - Read measured temperature. See: [`temperature()`](Lsm303agr::temperature).
 //!     - Configure FIFO. See: [`acc_set_fifo_mode()`](Lsm303agr::acc_set_fifo_mode).
+//!     - Get FIFO status and read FIFO samples. See: [`acc_fifo_status()`](Lsm303agr::acc_fifo_status) and [`acc_read_fifo()`](Lsm303agr::acc_read_fifo).
 //!     - Enable/disable interrupts. See: [`acc_enable_interrupt()`](Lsm303agr::acc_enable_interrupt).
+//!     - Enable/disable interrupts on the INT1 or INT2 pin. See: [`acc_enable_interrupt_on()`](Lsm303agr::acc_enable_interrupt_on).
+//!     - Set interrupt pin polarity. See: [`acc_set_interrupt_polarity()`](Lsm303agr::acc_set_interrupt_polarity).
+//!     - Configure inertial interrupt generators. See: [`acc_configure_int1()`](Lsm303agr::acc_configure_int1) and [`acc_configure_int2()`](Lsm303agr::acc_configure_int2).
+//!     - Enable free-fall detection. See: [`acc_enable_free_fall_detection()`](Lsm303agr::acc_enable_free_fall_detection).
+//!     - Get inertial interrupt sources. See: [`acc_int1_source()`](Lsm303agr::acc_int1_source) and [`acc_int2_source()`](Lsm303agr::acc_int2_source).
+//!     - Latch inertial interrupts and get/clear the latched source. See: [`acc_set_int1_latched()`](Lsm303agr::acc_set_int1_latched) and [`acc_take_int1_source()`](Lsm303agr::acc_take_int1_source).
+//!     - Enable/disable 4D detection. See: [`acc_set_int1_4d()`](Lsm303agr::acc_set_int1_4d) and [`acc_set_int2_4d()`](Lsm303agr::acc_set_int2_4d).
+//!     - Configure single/double-click detection. See: [`acc_configure_click()`](Lsm303agr::acc_configure_click).
+//!     - Get click source. See: [`acc_click_source()`](Lsm303agr::acc_click_source).
+//!     - Enable/disable sleep-to-wake/return-to-sleep activity detection. See: [`acc_enable_activity_detection()`](Lsm303agr::acc_enable_activity_detection).
+//!     - Configure the high-pass filter. See: [`acc_set_high_pass_filter()`](Lsm303agr::acc_set_high_pass_filter) and [`acc_enable_high_pass_filter()`](Lsm303agr::acc_enable_high_pass_filter).
+//!     - Reboot the accelerometer. See: [`acc_reboot()`](Lsm303agr::acc_reboot).
+//!     - Run the accelerometer self-test. See: [`acc_self_test()`](Lsm303agr::acc_self_test).
 //! - Magnetometer:
 //!     - Get the magnetometer status. See: [`mag_status()`](Lsm303agr::mag_status).
 //!     - Change into continuous/one-shot mode. See: [`into_mag_continuous()`](Lsm303agr::into_mag_continuous).
 //!     - Read measured magnetic field. See: [`magnetic_field()`](Lsm303agr::magnetic_field).
+//!     - Take a single measurement, waiting with a timeout. See: [`magnetic_field_blocking()`](Lsm303agr::magnetic_field_blocking).
 //!     - Set magnetometer mode and output data rate. See: [`set_mag_mode_and_odr()`](Lsm303agr::set_mag_mode_and_odr).
 //!     - Get magnetometer ID. See: [`magnetometer_id()`](Lsm303agr::magnetometer_id).
 //!     - Enable/disable magnetometer built in offset cancellation. See: [`enable_mag_offset_cancellation()`](Lsm303agr::enable_mag_offset_cancellation).
 //!     - Enable/disable magnetometer low-pass filter. See: [`mag_enable_low_pass_filter()`](Lsm303agr::mag_enable_low_pass_filter).
+//!     - Enable/disable magnetometer temperature compensation. This is done by `init()`. See: [`mag_enable_temp_compensation()`](Lsm303agr::mag_enable_temp_compensation).
+//!     - Set/get magnetometer hard-iron offset. See: [`set_mag_hard_iron_offset()`](Lsm303agr::set_mag_hard_iron_offset) and [`mag_hard_iron_offset()`](Lsm303agr::mag_hard_iron_offset).
+//!     - Reset the magnetometer. See: [`mag_soft_reset()`](Lsm303agr::mag_soft_reset).
+//!     - Run the magnetometer self-test. See: [`mag_self_test()`](Lsm303agr::mag_self_test).
+//!     - Configure the magnetometer threshold interrupt. See: [`mag_configure_int()`](Lsm303agr::mag_configure_int).
+//!     - Get the magnetometer threshold interrupt source. See: [`mag_int_source()`](Lsm303agr::mag_int_source).
+//!     - Select the data checked by the magnetometer threshold interrupt. See: [`mag_set_int_data_source()`](Lsm303agr::mag_set_int_data_source).
+//!     - Set the magnetometer set pulse frequency. See: [`mag_set_pulse_frequency()`](Lsm303agr::mag_set_pulse_frequency).
+//!     - Select big-endian magnetometer output. See: [`mag_set_endianness()`](Lsm303agr::mag_set_endianness).
+//!     - Disable the magnetometer I2C interface. This is done by `init()` on SPI. See: [`mag_disable_i2c()`](Lsm303agr::mag_disable_i2c).
 //!
 //! <!-- TODO
 //! [Introductory blog post](TODO)
@@ -101,27 +135,91 @@
 //! }
 //! # }
 //! ```
+//!
+//! ### embedded-hal 1.0
+//!
+//! With the `eh1` feature enabled, the driver can also be used with
+//! embedded-hal 1.0 implementations. The accelerometer and the magnetometer
+//! are then two `SpiDevice`s sharing a bus, each handling its own chip select:
+//!
+//! ```ignore
+//! let mut sensor = Lsm303agr::new_with_eh1_spi(accel_spi_device, mag_spi_device);
+//! ```
+//!
+//! Functions which need to wait for the device take an embedded-hal 0.2
+//! `DelayUs<u32>` implementation. An embedded-hal 1.0 `DelayNs`
+//! implementation can be passed by wrapping it in `Eh1Delay`:
+//!
+//! ```ignore
+//! sensor.set_accel_mode_and_odr(&mut Eh1Delay(&mut delay), mode, odr).unwrap();
+//! ```
+//!
+//! ### Async
+//!
+//! With the `async` feature enabled, `Lsm303agrAsync` provides an async API
+//! on top of embedded-hal-async I2C and `SpiDevice` implementations. Waiting
+//! is done through an embedded-hal-async `DelayNs` implementation:
+//!
+//! ```ignore
+//! let mut sensor = Lsm303agrAsync::new_with_i2c(i2c);
+//! sensor.init().await.unwrap();
+//! let data = sensor.magnetic_field(&mut delay).await.unwrap();
+//! ```
+//!
+//! As with the blocking driver, the magnetometer is changed to continuous
+//! mode with `into_mag_continuous()`:
+//!
+//! ```ignore
+//! let mut sensor = sensor.into_mag_continuous().await.ok().unwrap();
+//! let data = sensor.magnetic_field().await.unwrap();
+//! ```
+//!
+//! Instead of polling the status, the interrupt pins can be attached and
+//! awaited through embedded-hal-async `Wait` implementations:
+//!
+//! ```ignore
+//! let mut sensor = Lsm303agrAsync::new_with_i2c(i2c).with_int1_pin(int1);
+//! sensor.acc_enable_interrupt(Interrupt::DataReady1).await.unwrap();
+//! let status = sensor.wait_for_accel_data().await.unwrap();
+//! ```
 
 #![deny(unsafe_code, missing_docs)]
 #![no_std]
 #![doc(html_root_url = "https://docs.rs/lsm303agr/0.2.2")]
 
 use core::marker::PhantomData;
+mod accel_interrupts;
 mod accel_mode_and_odr;
+#[cfg(feature = "async")]
+mod async_interrupts;
+#[cfg(feature = "async")]
+mod asynch;
+#[cfg(feature = "eh1")]
+mod delay;
 mod device_impl;
 pub mod interface;
 mod mag_mode_change;
 mod magnetometer;
+mod reset;
+mod self_test;
 mod types;
+#[cfg(feature = "async")]
+pub use crate::asynch::Lsm303agrAsync;
+#[cfg(feature = "eh1")]
+pub use crate::delay::Eh1Delay;
 pub use crate::types::{
-    mode, AccelMode, AccelOutputDataRate, AccelScale, Acceleration, AccelerometerId, Error,
-    FifoMode, Interrupt, MagMode, MagOutputDataRate, MagneticField, MagnetometerId,
-    ModeChangeError, Status, Temperature, TemperatureStatus,
+    mode, AccelMode, AccelOutputDataRate, AccelScale, Acceleration, AccelerometerId, AnyMode,
+    AxisSet, ClickConfig, ClickSource, Endianness, Error, FifoMode, FifoStatus, HardIronOffset,
+    HighPassFilterCutoff, HighPassFilterMode, HighPassFilterTarget, Interrupt, InterruptConfig,
+    InterruptMode, InterruptPin, InterruptPolarity, InterruptSource, MagInterruptConfig,
+    MagInterruptDataSource, MagInterruptSource, MagMode, MagOutputDataRate, MagSetPulseFrequency,
+    MagneticField, MagnetometerId, ModeChangeError, SelfTestResult, Status, Temperature,
+    TemperatureStatus,
 };
 mod register_address;
 use crate::register_address::{
-    CfgRegAM, CfgRegBM, CfgRegCM, CtrlReg1A, CtrlReg3A, CtrlReg4A, CtrlReg5A, FifoCtrlRegA,
-    TempCfgRegA,
+    CfgRegAM, CfgRegBM, CfgRegCM, CtrlReg1A, CtrlReg2A, CtrlReg3A, CtrlReg4A, CtrlReg5A, CtrlReg6A,
+    FifoCtrlRegA, TempCfgRegA,
 };
 
 /// LSM303AGR device driver
@@ -130,9 +228,11 @@ pub struct Lsm303agr<DI, MODE> {
     /// Digital interface: I2C or SPI
     iface: DI,
     ctrl_reg1_a: CtrlReg1A,
+    ctrl_reg2_a: CtrlReg2A,
     ctrl_reg3_a: CtrlReg3A,
     ctrl_reg4_a: CtrlReg4A,
     ctrl_reg5_a: CtrlReg5A,
+    ctrl_reg6_a: CtrlReg6A,
     cfg_reg_a_m: CfgRegAM,
     cfg_reg_b_m: CfgRegBM,
     cfg_reg_c_m: CfgRegCM,
@@ -144,8 +244,19 @@ pub struct Lsm303agr<DI, MODE> {
 
 mod private {
     use crate::interface;
-    pub trait Sealed {}
+    pub trait Sealed {
+        /// Whether the device is accessed through SPI.
+        const SPI: bool = false;
+    }
 
-    impl<SPI, CSXL, CSMAG> Sealed for interface::SpiInterface<SPI, CSXL, CSMAG> {}
+    impl<SPI, CSXL, CSMAG> Sealed for interface::SpiInterface<SPI, CSXL, CSMAG> {
+        const SPI: bool = true;
+    }
     impl<I2C> Sealed for interface::I2cInterface<I2C> {}
+    #[cfg(feature = "eh1")]
+    impl<SPIXL, SPIMAG> Sealed for interface::Eh1SpiInterface<SPIXL, SPIMAG> {
+        const SPI: bool = true;
+    }
+    #[cfg(feature = "eh1")]
+    impl<I2C> Sealed for interface::Eh1I2cInterface<I2C> {}
 }
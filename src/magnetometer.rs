@@ -3,10 +3,44 @@ use embedded_hal::blocking::delay::DelayUs;
 use crate::{
     interface::{ReadData, WriteData},
     mode,
-    register_address::{CfgRegAM, CfgRegBM},
-    Error, Lsm303agr, MagMode, MagOutputDataRate, MagneticField,
+    register_address::{CfgRegAM, CfgRegBM, IntSourceRegM, IntThsHRegM, IntThsLRegM},
+    Endianness, Error, HardIronOffset, Lsm303agr, MagInterruptConfig, MagInterruptDataSource,
+    MagInterruptSource, MagMode, MagOutputDataRate, MagSetPulseFrequency, MagneticField,
 };
 
+/// Polling interval while waiting for a single measurement.
+pub(crate) const POLL_INTERVAL_US: u32 = 1_000;
+
+/// Number of status polls after the conversion time before giving up.
+pub(crate) const MAX_POLLS: u32 = 10;
+
+impl<DI, MODE> Lsm303agr<DI, MODE> {
+    /// Compute `CFG_REG_A_M` for changing the magnetometer mode and output
+    /// data rate, and the time to wait after writing it, if any.
+    pub(crate) fn mag_mode_and_odr_register(
+        &self,
+        mode: MagMode,
+        odr: MagOutputDataRate,
+    ) -> (CfgRegAM, Option<u32>) {
+        let old_mode = self.cfg_reg_a_m.mode();
+        let old_odr = self.cfg_reg_a_m.odr();
+
+        let rega = self.cfg_reg_a_m.with_mode(mode).with_odr(odr);
+
+        let offset_cancellation = self.cfg_reg_b_m.offset_cancellation();
+        let change_time = if old_mode != mode {
+            Some(rega.turn_on_time_us(offset_cancellation))
+        } else if old_odr != odr && offset_cancellation {
+            // Mode did not change, so only wait for 1/ODR ms.
+            Some(odr.turn_on_time_us_frac_1())
+        } else {
+            None
+        };
+
+        (rega, change_time)
+    }
+}
+
 impl<DI, CommE, PinE, MODE> Lsm303agr<DI, MODE>
 where
     DI: ReadData<Error = Error<CommE, PinE>> + WriteData<Error = Error<CommE, PinE>>,
@@ -20,21 +54,12 @@ where
         mode: MagMode,
         odr: MagOutputDataRate,
     ) -> Result<(), Error<CommE, PinE>> {
-        let rega = self.cfg_reg_a_m;
-
-        let old_mode = rega.mode();
-        let old_odr = rega.odr();
-
-        let rega = rega.with_mode(mode).with_odr(odr);
+        let (rega, change_time) = self.mag_mode_and_odr_register(mode, odr);
         self.iface.write_mag_register(rega)?;
         self.cfg_reg_a_m = rega;
 
-        let offset_cancellation = self.cfg_reg_b_m.offset_cancellation();
-        if old_mode != mode {
-            delay.delay_us(rega.turn_on_time_us(offset_cancellation));
-        } else if old_odr != odr && offset_cancellation {
-            // Mode did not change, so only wait for 1/ODR ms.
-            delay.delay_us(odr.turn_on_time_us_frac_1());
+        if let Some(change_time) = change_time {
+            delay.delay_us(change_time);
         }
 
         Ok(())
@@ -44,6 +69,79 @@ where
     pub fn get_mag_mode(&self) -> MagMode {
         self.cfg_reg_a_m.mode()
     }
+
+    /// Set the magnetometer hard-iron offset.
+    ///
+    /// The offset is subtracted from the measured magnetic field by the device
+    /// and is kept when changing between one-shot and continuous mode.
+    pub fn set_mag_hard_iron_offset(
+        &mut self,
+        offset: HardIronOffset,
+    ) -> Result<(), Error<CommE, PinE>> {
+        self.iface.write_mag_3_double_registers(offset)
+    }
+
+    /// Get the magnetometer hard-iron offset.
+    pub fn mag_hard_iron_offset(&mut self) -> Result<HardIronOffset, Error<CommE, PinE>> {
+        self.iface
+            .read_mag_3_double_registers::<HardIronOffset>(Endianness::Little)
+    }
+
+    /// Configure the magnetometer threshold interrupt.
+    ///
+    /// To drive the INT_MAG pin, use [`mag_enable_int()`](Lsm303agr::mag_enable_int).
+    pub fn mag_configure_int(
+        &mut self,
+        config: MagInterruptConfig,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let [low, high] = config.threshold().to_le_bytes();
+
+        self.iface
+            .write_mag_register(IntThsLRegM::from_bits_truncate(low))?;
+        self.iface
+            .write_mag_register(IntThsHRegM::from_bits_truncate(high))?;
+        self.iface.write_mag_register(config.ctrl())
+    }
+
+    /// Select the data checked by the magnetometer threshold interrupt.
+    pub fn mag_set_int_data_source(
+        &mut self,
+        source: MagInterruptDataSource,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let regb = self.cfg_reg_b_m.with_int_data_source(source);
+        self.iface.write_mag_register(regb)?;
+        self.cfg_reg_b_m = regb;
+
+        Ok(())
+    }
+
+    /// Set the magnetometer set pulse frequency.
+    ///
+    /// Releasing the set pulse only at power-on reduces the power consumption.
+    pub fn mag_set_pulse_frequency(
+        &mut self,
+        frequency: MagSetPulseFrequency,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let regb = self.cfg_reg_b_m.with_set_pulse_frequency(frequency);
+        self.iface.write_mag_register(regb)?;
+        self.cfg_reg_b_m = regb;
+
+        Ok(())
+    }
+
+    fn read_magnetic_field(&mut self) -> Result<MagneticField, Error<CommE, PinE>> {
+        self.iface
+            .read_mag_3_double_registers::<MagneticField>(self.cfg_reg_c_m.endianness())
+    }
+
+    /// Get the magnetometer threshold interrupt source.
+    ///
+    /// Reading the source clears a latched interrupt.
+    pub fn mag_int_source(&mut self) -> Result<MagInterruptSource, Error<CommE, PinE>> {
+        self.iface
+            .read_mag_register::<IntSourceRegM>()
+            .map(MagInterruptSource::new)
+    }
 }
 
 impl<DI, CommE, PinE> Lsm303agr<DI, mode::MagContinuous>
@@ -52,7 +150,7 @@ where
 {
     /// Get the measured magnetic field.
     pub fn magnetic_field(&mut self) -> Result<MagneticField, Error<CommE, PinE>> {
-        self.iface.read_mag_3_double_registers::<MagneticField>()
+        self.read_magnetic_field()
     }
 
     /// Enable the magnetometer's built in offset cancellation.
@@ -88,7 +186,7 @@ where
     pub fn magnetic_field(&mut self) -> nb::Result<MagneticField, Error<CommE, PinE>> {
         let status = self.mag_status()?;
         if status.xyz_new_data() {
-            Ok(self.iface.read_mag_3_double_registers::<MagneticField>()?)
+            Ok(self.read_magnetic_field()?)
         } else {
             let cfg = self.iface.read_mag_register::<CfgRegAM>()?;
             if !cfg.is_single_mode() {
@@ -101,6 +199,37 @@ where
         }
     }
 
+    /// Take a single measurement and wait for the measured magnetic field.
+    ///
+    /// The given `delay` is used to wait for the conversion time of the
+    /// current mode and output data rate. Afterwards the status is polled
+    /// every millisecond up to 10 times. If no new data is available by then,
+    /// `Error::Timeout` is returned.
+    pub fn magnetic_field_blocking<D: DelayUs<u32>>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<MagneticField, Error<CommE, PinE>> {
+        if self.mag_status()?.xyz_new_data() {
+            // Discard data of a previous measurement.
+            self.read_magnetic_field()?;
+        }
+
+        let cfg = self.cfg_reg_a_m.single_mode();
+        self.iface.write_mag_register(cfg)?;
+        self.cfg_reg_a_m = cfg;
+
+        delay.delay_us(cfg.turn_on_time_us(self.cfg_reg_b_m.offset_cancellation()));
+
+        for _ in 0..MAX_POLLS {
+            if self.mag_status()?.xyz_new_data() {
+                return self.read_magnetic_field();
+            }
+            delay.delay_us(POLL_INTERVAL_US);
+        }
+
+        Err(Error::Timeout)
+    }
+
     /// Enable the magnetometer's built in offset cancellation.
     ///
     /// Offset cancellation has to be **managed by the user** in **single measurement** (OneShot) mode averaging
@@ -1,6 +1,8 @@
 use crate::types::{
-    AccelOutputDataRate, AccelScale, AccelerometerId, FifoMode, Interrupt, MagMode,
-    MagOutputDataRate, MagnetometerId, StatusFlags,
+    AccelOutputDataRate, AccelScale, AccelerometerId, AxisSet, Endianness, FifoMode,
+    HighPassFilterCutoff, HighPassFilterMode, HighPassFilterTarget, Interrupt, InterruptPolarity,
+    InterruptSourceFlags, MagInterruptDataSource, MagMode, MagOutputDataRate, MagSetPulseFrequency,
+    MagnetometerId, StatusFlags,
 };
 
 pub trait RegRead<D = u8> {
@@ -118,6 +120,22 @@ impl Default for CtrlReg1A {
 }
 
 impl CtrlReg1A {
+    pub const fn odr(&self) -> Option<AccelOutputDataRate> {
+        Some(match self.intersection(Self::ODR).bits() >> 4 {
+            0b0001 => AccelOutputDataRate::Hz1,
+            0b0010 => AccelOutputDataRate::Hz10,
+            0b0011 => AccelOutputDataRate::Hz25,
+            0b0100 => AccelOutputDataRate::Hz50,
+            0b0101 => AccelOutputDataRate::Hz100,
+            0b0110 => AccelOutputDataRate::Hz200,
+            0b0111 => AccelOutputDataRate::Hz400,
+            0b1000 => AccelOutputDataRate::Khz1_620LowPower,
+            0b1001 if self.contains(Self::LPEN) => AccelOutputDataRate::Khz5_376LowPower,
+            0b1001 => AccelOutputDataRate::Khz1_344,
+            _ => return None,
+        })
+    }
+
     pub const fn with_odr(self, odr: AccelOutputDataRate) -> Self {
         let reg = self.difference(Self::ODR);
 
@@ -139,10 +157,34 @@ impl CtrlReg1A {
             }
         }
     }
+
+    pub const fn axes(&self) -> AxisSet {
+        AxisSet::none()
+            .x(self.contains(Self::XEN))
+            .y(self.contains(Self::YEN))
+            .z(self.contains(Self::ZEN))
+    }
+
+    pub const fn with_axes(self, axes: AxisSet) -> Self {
+        let mut reg = self.difference(Self::XEN.union(Self::YEN).union(Self::ZEN));
+
+        if axes.contains_x() {
+            reg = reg.union(Self::XEN);
+        }
+        if axes.contains_y() {
+            reg = reg.union(Self::YEN);
+        }
+        if axes.contains_z() {
+            reg = reg.union(Self::ZEN);
+        }
+
+        reg
+    }
 }
 
 register! {
   /// CTRL_REG2_A
+  #[derive(Default)]
   pub struct CtrlReg2A: 0x21 {
     const HPM1    = 0b10000000;
     const HPM0    = 0b01000000;
@@ -152,9 +194,49 @@ register! {
     const HPCLICK = 0b00000100;
     const HPIS2   = 0b00000010;
     const HPIS1   = 0b00000001;
+
+    const HPM = Self::HPM1.bits | Self::HPM0.bits;
+    const HPCF = Self::HPCF2.bits | Self::HPCF1.bits;
   }
 }
 
+impl CtrlReg2A {
+    pub const fn with_hp_mode(self, mode: HighPassFilterMode) -> Self {
+        match mode {
+            HighPassFilterMode::NormalWithReset => self.difference(Self::HPM),
+            HighPassFilterMode::Reference => self.difference(Self::HPM1).union(Self::HPM0),
+            HighPassFilterMode::Normal => self.union(Self::HPM1).difference(Self::HPM0),
+            HighPassFilterMode::AutoResetOnInterrupt => self.union(Self::HPM),
+        }
+    }
+
+    pub const fn with_hp_cutoff(self, cutoff: HighPassFilterCutoff) -> Self {
+        match cutoff {
+            HighPassFilterCutoff::Highest => self.difference(Self::HPCF),
+            HighPassFilterCutoff::High => self.difference(Self::HPCF2).union(Self::HPCF1),
+            HighPassFilterCutoff::Low => self.union(Self::HPCF2).difference(Self::HPCF1),
+            HighPassFilterCutoff::Lowest => self.union(Self::HPCF),
+        }
+    }
+
+    const fn hp_target(target: HighPassFilterTarget) -> Self {
+        match target {
+            HighPassFilterTarget::OutputData => Self::FDS,
+            HighPassFilterTarget::Click => Self::HPCLICK,
+            HighPassFilterTarget::Aoi1 => Self::HPIS1,
+            HighPassFilterTarget::Aoi2 => Self::HPIS2,
+        }
+    }
+
+    pub const fn with_hp_target(self, target: HighPassFilterTarget) -> Self {
+        self.union(Self::hp_target(target))
+    }
+
+    pub const fn without_hp_target(self, target: HighPassFilterTarget) -> Self {
+        self.difference(Self::hp_target(target))
+    }
+}
+
 register! {
   /// CTRL_REG3_A
   #[derive(Default)]
@@ -231,6 +313,21 @@ impl CtrlReg4A {
             AccelScale::G16 => self.union(Self::FS),
         }
     }
+
+    pub const fn endianness(&self) -> Endianness {
+        if self.contains(Self::BLE) {
+            Endianness::Big
+        } else {
+            Endianness::Little
+        }
+    }
+
+    pub const fn with_endianness(self, endianness: Endianness) -> Self {
+        match endianness {
+            Endianness::Little => self.difference(Self::BLE),
+            Endianness::Big => self.union(Self::BLE),
+        }
+    }
 }
 
 register! {
@@ -248,6 +345,7 @@ register! {
 
 register! {
   /// CTRL_REG6_A
+  #[derive(Default)]
   pub struct CtrlReg6A: 0x25 {
     const I2_CLICK_EN = 0b10000000;
     const I2_INT1     = 0b01000000;
@@ -258,6 +356,57 @@ register! {
   }
 }
 
+impl CtrlReg6A {
+    const fn interrupt(interrupt: Interrupt) -> Option<Self> {
+        match interrupt {
+            Interrupt::Click => Some(Self::I2_CLICK_EN),
+            Interrupt::Aoi1 => Some(Self::I2_INT1),
+            Interrupt::Aoi2 => Some(Self::I2_INT2),
+            Interrupt::DataReady1
+            | Interrupt::DataReady2
+            | Interrupt::FifoWatermark
+            | Interrupt::FifoOverrun => None,
+        }
+    }
+
+    /// Returns `None` if the interrupt cannot be routed to the INT2 pin.
+    pub const fn with_interrupt(self, interrupt: Interrupt) -> Option<Self> {
+        match Self::interrupt(interrupt) {
+            Some(bits) => Some(self.union(bits)),
+            None => None,
+        }
+    }
+
+    /// Returns `None` if the interrupt cannot be routed to the INT2 pin.
+    pub const fn without_interrupt(self, interrupt: Interrupt) -> Option<Self> {
+        match Self::interrupt(interrupt) {
+            Some(bits) => Some(self.difference(bits)),
+            None => None,
+        }
+    }
+
+    pub const fn with_polarity(self, polarity: InterruptPolarity) -> Self {
+        match polarity {
+            InterruptPolarity::ActiveHigh => self.difference(Self::H_LACTIVE),
+            InterruptPolarity::ActiveLow => self.union(Self::H_LACTIVE),
+        }
+    }
+}
+
+register! {
+  /// REFERENCE/DATACAPTURE_A
+  pub struct ReferenceA: 0x26 {
+    const REF7 = 0b10000000;
+    const REF6 = 0b01000000;
+    const REF5 = 0b00100000;
+    const REF4 = 0b00010000;
+    const REF3 = 0b00001000;
+    const REF2 = 0b00000100;
+    const REF1 = 0b00000010;
+    const REF0 = 0b00000001;
+  }
+}
+
 register! {
   /// STATUS_REG_A
   pub type StatusRegA: 0x27 = StatusFlags;
@@ -304,6 +453,7 @@ impl FifoCtrlRegA {
 
 register! {
   /// FIFO_SRC_REG_A
+  #[derive(Default)]
   pub struct FifoSrcRegA: 0x2F {
     const WTM       = 0b10000000;
     const OVRN_FIFO = 0b01000000;
@@ -313,11 +463,19 @@ register! {
     const FSS2      = 0b00000100;
     const FSS1      = 0b00000010;
     const FSS0      = 0b00000001;
+
+    const FSS = Self::FSS4.bits | Self::FSS3.bits | Self::FSS2.bits | Self::FSS1.bits | Self::FSS0.bits;
   }
 }
 
+impl FifoSrcRegA {
+    /// Number of samples the FIFO can hold.
+    pub const CAPACITY: u8 = 32;
+}
+
 register! {
   /// INT1_CFG_A
+  #[derive(Default)]
   pub struct Int1CfgA: 0x30 {
     const AOI       = 0b10000000;
     const D6        = 0b01000000;
@@ -338,14 +496,203 @@ register! {
 
 register! {
   /// INT1_SRC_A
-  pub struct Int1SrcA: 0x31 {
-    const IA = 0b01000000;
-    const ZH = 0b00100000;
-    const ZL = 0b00010000;
-    const YH = 0b00001000;
-    const YL = 0b00000100;
-    const XH = 0b00000010;
-    const XL = 0b00000001;
+  pub type Int1SrcA: 0x31 = InterruptSourceFlags;
+}
+
+register! {
+  /// INT1_THS_A
+  #[derive(Default)]
+  pub struct Int1ThsA: 0x32 {
+    const THS6 = 0b01000000;
+    const THS5 = 0b00100000;
+    const THS4 = 0b00010000;
+    const THS3 = 0b00001000;
+    const THS2 = 0b00000100;
+    const THS1 = 0b00000010;
+    const THS0 = 0b00000001;
+  }
+}
+
+register! {
+  /// INT1_DURATION_A
+  #[derive(Default)]
+  pub struct Int1DurationA: 0x33 {
+    const D6 = 0b01000000;
+    const D5 = 0b00100000;
+    const D4 = 0b00010000;
+    const D3 = 0b00001000;
+    const D2 = 0b00000100;
+    const D1 = 0b00000010;
+    const D0 = 0b00000001;
+  }
+}
+
+register! {
+  /// INT2_CFG_A
+  #[derive(Default)]
+  pub struct Int2CfgA: 0x34 {
+    const AOI       = 0b10000000;
+    const D6        = 0b01000000;
+    const ZHIE      = 0b00100000;
+    const ZUPE      = Self::ZHIE.bits;
+    const ZLIE      = 0b00010000;
+    const ZDOWNE    = Self::ZLIE.bits;
+    const YHIE      = 0b00001000;
+    const YUPE      = Self::YHIE.bits;
+    const YLIE      = 0b00000100;
+    const YDOWNE    = Self::YLIE.bits;
+    const XHIE      = 0b00000010;
+    const XUPE      = Self::XHIE.bits;
+    const XLIE      = 0b00000001;
+    const XDOWNE    = Self::XLIE.bits;
+  }
+}
+
+register! {
+  /// INT2_SRC_A
+  pub type Int2SrcA: 0x35 = InterruptSourceFlags;
+}
+
+register! {
+  /// INT2_THS_A
+  #[derive(Default)]
+  pub struct Int2ThsA: 0x36 {
+    const THS6 = 0b01000000;
+    const THS5 = 0b00100000;
+    const THS4 = 0b00010000;
+    const THS3 = 0b00001000;
+    const THS2 = 0b00000100;
+    const THS1 = 0b00000010;
+    const THS0 = 0b00000001;
+  }
+}
+
+register! {
+  /// INT2_DURATION_A
+  #[derive(Default)]
+  pub struct Int2DurationA: 0x37 {
+    const D6 = 0b01000000;
+    const D5 = 0b00100000;
+    const D4 = 0b00010000;
+    const D3 = 0b00001000;
+    const D2 = 0b00000100;
+    const D1 = 0b00000010;
+    const D0 = 0b00000001;
+  }
+}
+
+register! {
+  /// CLICK_CFG_A
+  #[derive(Default)]
+  pub struct ClickCfgA: 0x38 {
+    const ZD = 0b00100000;
+    const ZS = 0b00010000;
+    const YD = 0b00001000;
+    const YS = 0b00000100;
+    const XD = 0b00000010;
+    const XS = 0b00000001;
+  }
+}
+
+register! {
+  /// CLICK_SRC_A
+  #[derive(Default)]
+  pub struct ClickSrcA: 0x39 {
+    const IA     = 0b01000000;
+    const DCLICK = 0b00100000;
+    const SCLICK = 0b00010000;
+    const SIGN   = 0b00001000;
+    const Z      = 0b00000100;
+    const Y      = 0b00000010;
+    const X      = 0b00000001;
+  }
+}
+
+register! {
+  /// CLICK_THS_A
+  #[derive(Default)]
+  pub struct ClickThsA: 0x3A {
+    const LIR_CLICK = 0b10000000;
+    const THS6      = 0b01000000;
+    const THS5      = 0b00100000;
+    const THS4      = 0b00010000;
+    const THS3      = 0b00001000;
+    const THS2      = 0b00000100;
+    const THS1      = 0b00000010;
+    const THS0      = 0b00000001;
+  }
+}
+
+register! {
+  /// TIME_LIMIT_A
+  #[derive(Default)]
+  pub struct TimeLimitA: 0x3B {
+    const TLI6 = 0b01000000;
+    const TLI5 = 0b00100000;
+    const TLI4 = 0b00010000;
+    const TLI3 = 0b00001000;
+    const TLI2 = 0b00000100;
+    const TLI1 = 0b00000010;
+    const TLI0 = 0b00000001;
+  }
+}
+
+register! {
+  /// TIME_LATENCY_A
+  #[derive(Default)]
+  pub struct TimeLatencyA: 0x3C {
+    const TLA7 = 0b10000000;
+    const TLA6 = 0b01000000;
+    const TLA5 = 0b00100000;
+    const TLA4 = 0b00010000;
+    const TLA3 = 0b00001000;
+    const TLA2 = 0b00000100;
+    const TLA1 = 0b00000010;
+    const TLA0 = 0b00000001;
+  }
+}
+
+register! {
+  /// TIME_WINDOW_A
+  #[derive(Default)]
+  pub struct TimeWindowA: 0x3D {
+    const TW7 = 0b10000000;
+    const TW6 = 0b01000000;
+    const TW5 = 0b00100000;
+    const TW4 = 0b00010000;
+    const TW3 = 0b00001000;
+    const TW2 = 0b00000100;
+    const TW1 = 0b00000010;
+    const TW0 = 0b00000001;
+  }
+}
+
+register! {
+  /// ACT_THS_A
+  #[derive(Default)]
+  pub struct ActThsA: 0x3E {
+    const ACTH6 = 0b01000000;
+    const ACTH5 = 0b00100000;
+    const ACTH4 = 0b00010000;
+    const ACTH3 = 0b00001000;
+    const ACTH2 = 0b00000100;
+    const ACTH1 = 0b00000010;
+    const ACTH0 = 0b00000001;
+  }
+}
+
+register! {
+  /// ACT_DUR_A
+  #[derive(Default)]
+  pub struct ActDurA: 0x3F {
+    const ACTD7 = 0b10000000;
+    const ACTD6 = 0b01000000;
+    const ACTD5 = 0b00100000;
+    const ACTD4 = 0b00010000;
+    const ACTD3 = 0b00001000;
+    const ACTD2 = 0b00000100;
+    const ACTD1 = 0b00000010;
+    const ACTD0 = 0b00000001;
   }
 }
 
@@ -401,6 +748,10 @@ impl CfgRegAM {
         self.difference(Self::MD1).difference(Self::MD0) // 0b00
     }
 
+    pub const fn is_continuous_mode(&self) -> bool {
+        !self.intersects(Self::MD)
+    }
+
     pub const fn is_single_mode(&self) -> bool {
         !self.contains(CfgRegAM::MD1) && self.contains(CfgRegAM::MD0)
     }
@@ -463,6 +814,20 @@ impl CfgRegBM {
     pub const fn offset_cancellation(&self) -> bool {
         self.contains(CfgRegBM::OFF_CANC)
     }
+
+    pub const fn with_int_data_source(self, source: MagInterruptDataSource) -> Self {
+        match source {
+            MagInterruptDataSource::Uncorrected => self.difference(Self::INT_ON_DATA_OFF),
+            MagInterruptDataSource::HardIronCorrected => self.union(Self::INT_ON_DATA_OFF),
+        }
+    }
+
+    pub const fn with_set_pulse_frequency(self, frequency: MagSetPulseFrequency) -> Self {
+        match frequency {
+            MagSetPulseFrequency::Every63Odr => self.difference(Self::SET_FREQ),
+            MagSetPulseFrequency::PowerOnOnly => self.union(Self::SET_FREQ),
+        }
+    }
 }
 
 register! {
@@ -478,6 +843,78 @@ register! {
   }
 }
 
+impl CfgRegCM {
+    pub const fn endianness(&self) -> Endianness {
+        if self.contains(Self::BLE) {
+            Endianness::Big
+        } else {
+            Endianness::Little
+        }
+    }
+
+    pub const fn with_endianness(self, endianness: Endianness) -> Self {
+        match endianness {
+            Endianness::Little => self.difference(Self::BLE),
+            Endianness::Big => self.union(Self::BLE),
+        }
+    }
+}
+
+register! {
+  /// INT_CTRL_REG_M
+  pub struct IntCtrlRegM: 0x63 {
+    const XIEN = 0b10000000;
+    const YIEN = 0b01000000;
+    const ZIEN = 0b00100000;
+    const IEA  = 0b00000100;
+    const IEL  = 0b00000010;
+    const IEN  = 0b00000001;
+  }
+}
+
+register! {
+  /// INT_SOURCE_REG_M
+  #[derive(Default)]
+  pub struct IntSourceRegM: 0x64 {
+    const P_TH_S_X = 0b10000000;
+    const P_TH_S_Y = 0b01000000;
+    const P_TH_S_Z = 0b00100000;
+    const N_TH_S_X = 0b00010000;
+    const N_TH_S_Y = 0b00001000;
+    const N_TH_S_Z = 0b00000100;
+    const MROI     = 0b00000010;
+    const INT      = 0b00000001;
+  }
+}
+
+register! {
+  /// INT_THS_L_REG_M
+  pub struct IntThsLRegM: 0x65 {
+    const THS7 = 0b10000000;
+    const THS6 = 0b01000000;
+    const THS5 = 0b00100000;
+    const THS4 = 0b00010000;
+    const THS3 = 0b00001000;
+    const THS2 = 0b00000100;
+    const THS1 = 0b00000010;
+    const THS0 = 0b00000001;
+  }
+}
+
+register! {
+  /// INT_THS_H_REG_M
+  pub struct IntThsHRegM: 0x66 {
+    const THS15 = 0b10000000;
+    const THS14 = 0b01000000;
+    const THS13 = 0b00100000;
+    const THS12 = 0b00010000;
+    const THS11 = 0b00001000;
+    const THS10 = 0b00000100;
+    const THS9  = 0b00000010;
+    const THS8  = 0b00000001;
+  }
+}
+
 register! {
   /// STATUS_REG_M
   pub type StatusRegM: 0x67 = StatusFlags;
@@ -509,6 +946,69 @@ mod tests {
         check_odr(AccelOutputDataRate::Hz100, 0b0101);
         check_odr(AccelOutputDataRate::Hz200, 0b0110);
         check_odr(AccelOutputDataRate::Hz400, 0b0111);
+
+        assert_eq!(ctrl.odr(), None);
+        for odr in [
+            AccelOutputDataRate::Hz1,
+            AccelOutputDataRate::Hz10,
+            AccelOutputDataRate::Hz25,
+            AccelOutputDataRate::Hz50,
+            AccelOutputDataRate::Hz100,
+            AccelOutputDataRate::Hz200,
+            AccelOutputDataRate::Hz400,
+            AccelOutputDataRate::Khz1_344,
+            AccelOutputDataRate::Khz1_620LowPower,
+            AccelOutputDataRate::Khz5_376LowPower,
+        ] {
+            assert_eq!(ctrl.with_odr(odr).odr(), Some(odr));
+        }
+    }
+
+    #[test]
+    fn ctrl_reg_2_a() {
+        let ctrl = CtrlReg2A::default();
+        assert_eq!(ctrl.bits(), 0);
+
+        let check_mode = |mode, value| {
+            assert_eq!(
+                ctrl.with_hp_mode(mode).intersection(CtrlReg2A::HPM).bits() >> 6,
+                value
+            );
+        };
+
+        check_mode(HighPassFilterMode::NormalWithReset, 0b00);
+        check_mode(HighPassFilterMode::Reference, 0b01);
+        check_mode(HighPassFilterMode::Normal, 0b10);
+        check_mode(HighPassFilterMode::AutoResetOnInterrupt, 0b11);
+
+        let check_cutoff = |cutoff, value| {
+            assert_eq!(
+                ctrl.with_hp_cutoff(cutoff)
+                    .intersection(CtrlReg2A::HPCF)
+                    .bits()
+                    >> 4,
+                value
+            );
+        };
+
+        check_cutoff(HighPassFilterCutoff::Highest, 0b00);
+        check_cutoff(HighPassFilterCutoff::High, 0b01);
+        check_cutoff(HighPassFilterCutoff::Low, 0b10);
+        check_cutoff(HighPassFilterCutoff::Lowest, 0b11);
+
+        let ctrl_all = CtrlReg2A::from_bits_truncate(0b1111);
+
+        let mut bits = 0b1000;
+        for target in [
+            HighPassFilterTarget::OutputData,
+            HighPassFilterTarget::Click,
+            HighPassFilterTarget::Aoi2,
+            HighPassFilterTarget::Aoi1,
+        ] {
+            assert_eq!(ctrl.with_hp_target(target).bits(), bits);
+            assert_eq!(ctrl_all.without_hp_target(target).bits(), (!bits) & 0b1111);
+            bits >>= 1;
+        }
     }
 
     #[test]
@@ -535,6 +1035,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn ctrl_reg_6_a() {
+        let ctrl = CtrlReg6A::default();
+        let ctrl_all = CtrlReg6A::from_bits_truncate(0b11100000);
+
+        let mut bits = 0b10000000;
+        for interrupt in [Interrupt::Click, Interrupt::Aoi1, Interrupt::Aoi2] {
+            assert_eq!(ctrl.with_interrupt(interrupt).unwrap().bits(), bits);
+            assert_eq!(
+                ctrl_all.without_interrupt(interrupt).unwrap().bits(),
+                (!bits) & 0b11100000
+            );
+            bits >>= 1;
+        }
+
+        for interrupt in [
+            Interrupt::DataReady1,
+            Interrupt::DataReady2,
+            Interrupt::FifoWatermark,
+            Interrupt::FifoOverrun,
+        ] {
+            assert_eq!(ctrl.with_interrupt(interrupt), None);
+            assert_eq!(ctrl_all.without_interrupt(interrupt), None);
+        }
+
+        let ctrl_low = ctrl.with_polarity(InterruptPolarity::ActiveLow);
+        assert_eq!(ctrl_low, CtrlReg6A::H_LACTIVE);
+        assert_eq!(ctrl_low.with_polarity(InterruptPolarity::ActiveHigh), ctrl);
+    }
+
     #[test]
     fn ctrl_reg_4_a() {
         let ctrl = CtrlReg4A::default();
@@ -552,6 +1082,34 @@ mod tests {
         assert_eq!(ctrl_g2.scale(), AccelScale::G2);
     }
 
+    #[test]
+    fn ctrl_reg_1_a_axes() {
+        let ctrl = CtrlReg1A::default();
+        assert_eq!(ctrl.axes(), AxisSet::all());
+
+        let ctrl_z = ctrl.with_axes(AxisSet::none().z(true));
+        assert_eq!(ctrl_z, CtrlReg1A::ZEN);
+        assert_eq!(ctrl_z.axes(), AxisSet::none().z(true));
+        assert_eq!(ctrl_z.with_axes(AxisSet::all()), ctrl);
+    }
+
+    #[test]
+    fn endianness() {
+        let ctrl = CtrlReg4A::default();
+        assert_eq!(ctrl.endianness(), Endianness::Little);
+        let ctrl_big = ctrl.with_endianness(Endianness::Big);
+        assert_eq!(ctrl_big, CtrlReg4A::BLE);
+        assert_eq!(ctrl_big.endianness(), Endianness::Big);
+        assert_eq!(ctrl_big.with_endianness(Endianness::Little), ctrl);
+
+        let cfg = CfgRegCM::default();
+        assert_eq!(cfg.endianness(), Endianness::Little);
+        let cfg_big = cfg.with_endianness(Endianness::Big);
+        assert_eq!(cfg_big, CfgRegCM::BLE);
+        assert_eq!(cfg_big.endianness(), Endianness::Big);
+        assert_eq!(cfg_big.with_endianness(Endianness::Little), cfg);
+    }
+
     #[test]
     fn cfg_reg_a_m() {
         let cfg = CfgRegAM::default();
@@ -576,4 +1134,23 @@ mod tests {
         assert!(!cfg.is_single_mode());
         assert!(!cfg.is_idle_mode());
     }
+
+    #[test]
+    fn cfg_reg_b_m() {
+        let cfg = CfgRegBM::default();
+
+        let cfg_corrected = cfg.with_int_data_source(MagInterruptDataSource::HardIronCorrected);
+        assert_eq!(cfg_corrected, CfgRegBM::INT_ON_DATA_OFF);
+        assert_eq!(
+            cfg_corrected.with_int_data_source(MagInterruptDataSource::Uncorrected),
+            cfg
+        );
+
+        let cfg_power_on = cfg.with_set_pulse_frequency(MagSetPulseFrequency::PowerOnOnly);
+        assert_eq!(cfg_power_on, CfgRegBM::SET_FREQ);
+        assert_eq!(
+            cfg_power_on.with_set_pulse_frequency(MagSetPulseFrequency::Every63Odr),
+            cfg
+        );
+    }
 }
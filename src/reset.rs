@@ -0,0 +1,119 @@
+use embedded_hal::blocking::delay::DelayUs;
+
+use crate::{
+    interface::{ReadData, WriteData},
+    mode,
+    register_address::{
+        CfgRegAM, CfgRegBM, CfgRegCM, CtrlReg1A, CtrlReg2A, CtrlReg3A, CtrlReg4A, CtrlReg5A,
+        CtrlReg6A, FifoCtrlRegA, TempCfgRegA,
+    },
+    Error, Lsm303agr, ModeChangeError,
+};
+
+/// Time to wait for the accelerometer memory content to be reloaded.
+const ACCEL_BOOT_TIME_US: u32 = 5_000;
+
+/// Time to wait for the magnetometer registers to be reset.
+const MAG_RESET_TIME_US: u32 = 10_000;
+
+impl<DI, CommE, PinE, MODE> Lsm303agr<DI, MODE>
+where
+    DI: ReadData<Error = Error<CommE, PinE>> + WriteData<Error = Error<CommE, PinE>>,
+{
+    /// Reboot the accelerometer memory content and reset its configuration.
+    ///
+    /// The reboot only reloads the trimming parameters, so the accelerometer
+    /// control registers are then written with their power-on default values.
+    /// Afterwards the accelerometer is powered down.
+    ///
+    /// The 3-wire SPI mode is kept, if enabled.
+    pub fn acc_reboot<D: DelayUs<u32>>(&mut self, delay: &mut D) -> Result<(), Error<CommE, PinE>> {
+        let reg5 = self.ctrl_reg5_a.union(CtrlReg5A::BOOT);
+        self.iface.write_accel_register(reg5)?;
+
+        delay.delay_us(ACCEL_BOOT_TIME_US);
+
+        let reg1 = CtrlReg1A::default();
+        self.iface.write_accel_register(reg1)?;
+        self.ctrl_reg1_a = reg1;
+        self.accel_odr = None;
+
+        let reg2 = CtrlReg2A::default();
+        self.iface.write_accel_register(reg2)?;
+        self.ctrl_reg2_a = reg2;
+
+        let reg3 = CtrlReg3A::default();
+        self.iface.write_accel_register(reg3)?;
+        self.ctrl_reg3_a = reg3;
+
+        let reg4 = CtrlReg4A::default() | (self.ctrl_reg4_a & CtrlReg4A::SPI_ENABLE);
+        self.iface.write_accel_register(reg4)?;
+        self.ctrl_reg4_a = reg4;
+
+        let reg5 = CtrlReg5A::default();
+        self.iface.write_accel_register(reg5)?;
+        self.ctrl_reg5_a = reg5;
+
+        let reg6 = CtrlReg6A::default();
+        self.iface.write_accel_register(reg6)?;
+        self.ctrl_reg6_a = reg6;
+
+        let temp_cfg_reg = TempCfgRegA::default();
+        self.iface.write_accel_register(temp_cfg_reg)?;
+        self.temp_cfg_reg_a = temp_cfg_reg;
+
+        let fifo_ctrl = FifoCtrlRegA::default();
+        self.iface.write_accel_register(fifo_ctrl)?;
+        self.fifo_ctrl_reg_a = fifo_ctrl;
+
+        Ok(())
+    }
+
+    /// Reset the magnetometer configuration and user registers.
+    ///
+    /// This also clears the hard-iron offset. Afterwards the magnetometer is
+    /// in idle mode, so the driver is returned in one-shot mode.
+    ///
+    /// The magnetometer I2C interface is disabled again, if it was disabled.
+    pub fn mag_soft_reset<D: DelayUs<u32>>(
+        mut self,
+        delay: &mut D,
+    ) -> Result<Lsm303agr<DI, mode::MagOneShot>, ModeChangeError<CommE, PinE, Self>> {
+        let regc = CfgRegCM::default() | (self.cfg_reg_c_m & CfgRegCM::I2C_DIS);
+
+        let rega = self.cfg_reg_a_m.union(CfgRegAM::SOFT_RST);
+        if let Err(error) = self.iface.write_mag_register(rega) {
+            return Err(ModeChangeError { error, dev: self });
+        }
+
+        delay.delay_us(MAG_RESET_TIME_US);
+
+        self.cfg_reg_a_m = CfgRegAM::default();
+        self.cfg_reg_b_m = CfgRegBM::default();
+        self.cfg_reg_c_m = CfgRegCM::default();
+
+        if self.cfg_reg_c_m != regc {
+            if let Err(error) = self.iface.write_mag_register(regc) {
+                return Err(ModeChangeError { error, dev: self });
+            }
+            self.cfg_reg_c_m = regc;
+        }
+
+        Ok(self.into_mode())
+    }
+
+    /// Reboot the accelerometer and reset the magnetometer.
+    ///
+    /// See [`acc_reboot()`](Lsm303agr::acc_reboot) and
+    /// [`mag_soft_reset()`](Lsm303agr::mag_soft_reset).
+    pub fn reset<D: DelayUs<u32>>(
+        mut self,
+        delay: &mut D,
+    ) -> Result<Lsm303agr<DI, mode::MagOneShot>, ModeChangeError<CommE, PinE, Self>> {
+        if let Err(error) = self.acc_reboot(delay) {
+            return Err(ModeChangeError { error, dev: self });
+        }
+
+        self.mag_soft_reset(delay)
+    }
+}
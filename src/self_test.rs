@@ -0,0 +1,281 @@
+use embedded_hal::blocking::delay::DelayUs;
+
+use crate::{
+    interface::{ReadData, WriteData},
+    register_address::{CfgRegAM, CfgRegBM, CfgRegCM, CtrlReg1A, CtrlReg2A, CtrlReg4A},
+    AccelMode, AccelOutputDataRate, AccelScale, Error, FifoMode, Lsm303agr, MagOutputDataRate,
+    MagneticField, SelfTestResult,
+};
+
+/// Polling interval while waiting for new data.
+const POLL_INTERVAL_US: u32 = 1_000;
+
+/// Maximum number of status polls while waiting for new data, i.e. ten
+/// output data periods at 100 Hz.
+const MAX_POLLS: u32 = 100;
+
+/// Number of accelerometer samples averaged with and without self-test enabled.
+const ACCEL_SAMPLES: i32 = 5;
+
+/// Time for the accelerometer output to settle after changing the configuration.
+const ACCEL_SETTLING_TIME_US: u32 = 90_000;
+
+/// Accelerometer self-test output change limits in normal mode at ±2 *g*,
+/// 17 to 360 LSB at 4 m*g*/LSB.
+const ACCEL_MIN_MG: i32 = 68;
+const ACCEL_MAX_MG: i32 = 1440;
+
+/// Number of magnetometer samples averaged with and without self-test enabled.
+const MAG_SAMPLES: i32 = 50;
+
+/// Time for the magnetometer output to settle without and with self-test enabled.
+const MAG_SETTLING_TIME_US: u32 = 20_000;
+const MAG_ST_SETTLING_TIME_US: u32 = 60_000;
+
+/// Magnetometer self-test output change limits, 15 to 500 LSB at 150 nT/LSB.
+const MAG_MIN_NT: i32 = 2_250;
+const MAG_MAX_NT: i32 = 75_000;
+
+impl<DI, CommE, PinE, MODE> Lsm303agr<DI, MODE>
+where
+    DI: ReadData<Error = Error<CommE, PinE>> + WriteData<Error = Error<CommE, PinE>>,
+{
+    /// Run the accelerometer self-test.
+    ///
+    /// The output is averaged over 5 samples with self-test disabled and
+    /// 5 samples with positive self-test enabled in normal mode at 100 Hz
+    /// and ±2 *g*, with the high-pass filter and the FIFO bypassed. The
+    /// previous configuration is restored afterwards.
+    ///
+    /// The given `delay` is used to wait for the output to settle, between
+    /// polling the status for new data and for the turn-on time after
+    /// restoring the previous mode. Returns `Error::Timeout` if no new data
+    /// is available after 100 polls.
+    pub fn acc_self_test<D: DelayUs<u32>>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<SelfTestResult, Error<CommE, PinE>> {
+        let mode = self.get_accel_mode();
+        let reg1 = self.ctrl_reg1_a;
+        let reg2 = self.ctrl_reg2_a;
+        let reg4 = self.ctrl_reg4_a;
+        let fifo_ctrl = self.fifo_ctrl_reg_a;
+        let odr = self.accel_odr;
+
+        let result = self.acc_self_test_run(delay);
+
+        // Same order as in `set_accel_mode_and_odr`.
+        if mode != AccelMode::HighResolution {
+            self.iface.write_accel_register(reg4)?;
+            self.ctrl_reg4_a = reg4;
+        }
+
+        self.iface.write_accel_register(reg1)?;
+        self.ctrl_reg1_a = reg1;
+        self.accel_odr = odr;
+
+        if mode == AccelMode::HighResolution {
+            self.iface.write_accel_register(reg4)?;
+            self.ctrl_reg4_a = reg4;
+        }
+
+        self.iface.write_accel_register(reg2)?;
+        self.ctrl_reg2_a = reg2;
+        self.iface.write_accel_register(fifo_ctrl)?;
+        self.fifo_ctrl_reg_a = fifo_ctrl;
+
+        if let Some(odr) = odr {
+            delay.delay_us(AccelMode::Normal.change_time_us(mode, odr));
+        }
+
+        Ok(SelfTestResult::new(result?, ACCEL_MIN_MG, ACCEL_MAX_MG))
+    }
+
+    fn acc_self_test_run<D: DelayUs<u32>>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(i32, i32, i32), Error<CommE, PinE>> {
+        let reg2 = CtrlReg2A::empty();
+        self.iface.write_accel_register(reg2)?;
+        self.ctrl_reg2_a = reg2;
+
+        let fifo_ctrl = self.fifo_ctrl_reg_a.with_mode(FifoMode::Bypass);
+        self.iface.write_accel_register(fifo_ctrl)?;
+        self.fifo_ctrl_reg_a = fifo_ctrl;
+
+        let reg4 = self
+            .ctrl_reg4_a
+            .difference(CtrlReg4A::HR | CtrlReg4A::BLE | CtrlReg4A::ST)
+            .union(CtrlReg4A::BDU)
+            .with_scale(AccelScale::G2);
+        self.iface.write_accel_register(reg4)?;
+        self.ctrl_reg4_a = reg4;
+
+        let reg1 = CtrlReg1A::default().with_odr(AccelOutputDataRate::Hz100);
+        self.iface.write_accel_register(reg1)?;
+        self.ctrl_reg1_a = reg1;
+        self.accel_odr = Some(AccelOutputDataRate::Hz100);
+
+        delay.delay_us(ACCEL_SETTLING_TIME_US);
+        let no_st = self.acc_self_test_average(delay)?;
+
+        let reg4 = reg4.union(CtrlReg4A::ST0);
+        self.iface.write_accel_register(reg4)?;
+        self.ctrl_reg4_a = reg4;
+
+        delay.delay_us(ACCEL_SETTLING_TIME_US);
+        let st = self.acc_self_test_average(delay)?;
+
+        Ok(delta(no_st, st))
+    }
+
+    fn acc_self_test_average<D: DelayUs<u32>>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(i32, i32, i32), Error<CommE, PinE>> {
+        // Discard the first sample.
+        self.acc_wait_for_data(delay)?;
+        self.acceleration()?;
+
+        let mut sum = (0, 0, 0);
+        for _ in 0..ACCEL_SAMPLES {
+            self.acc_wait_for_data(delay)?;
+            let (x, y, z) = self.acceleration()?.xyz_mg();
+            sum = (sum.0 + x, sum.1 + y, sum.2 + z);
+        }
+
+        Ok((
+            sum.0 / ACCEL_SAMPLES,
+            sum.1 / ACCEL_SAMPLES,
+            sum.2 / ACCEL_SAMPLES,
+        ))
+    }
+
+    fn acc_wait_for_data<D: DelayUs<u32>>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), Error<CommE, PinE>> {
+        for _ in 0..MAX_POLLS {
+            if self.accel_status()?.xyz_new_data() {
+                return Ok(());
+            }
+            delay.delay_us(POLL_INTERVAL_US);
+        }
+
+        Err(Error::Timeout)
+    }
+
+    /// Run the magnetometer self-test.
+    ///
+    /// The output is averaged over 50 samples with self-test disabled and
+    /// 50 samples with self-test enabled in continuous mode at 100 Hz with
+    /// temperature compensation and offset cancellation. The previous
+    /// configuration, including the one-shot or continuous mode, is restored
+    /// afterwards.
+    ///
+    /// The given `delay` is used to wait for the output to settle and
+    /// between polling the status for new data. Returns `Error::Timeout`
+    /// if no new data is available after 100 polls.
+    pub fn mag_self_test<D: DelayUs<u32>>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<SelfTestResult, Error<CommE, PinE>> {
+        let rega = self.cfg_reg_a_m;
+        let regb = self.cfg_reg_b_m;
+        let regc = self.cfg_reg_c_m;
+
+        let result = self.mag_self_test_run(delay);
+
+        self.iface.write_mag_register(regc)?;
+        self.cfg_reg_c_m = regc;
+        self.iface.write_mag_register(regb)?;
+        self.cfg_reg_b_m = regb;
+        self.iface.write_mag_register(rega)?;
+        self.cfg_reg_a_m = rega;
+
+        Ok(SelfTestResult::new(result?, MAG_MIN_NT, MAG_MAX_NT))
+    }
+
+    fn mag_self_test_run<D: DelayUs<u32>>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(i32, i32, i32), Error<CommE, PinE>> {
+        let regc = self
+            .cfg_reg_c_m
+            .difference(CfgRegCM::BLE | CfgRegCM::SELF_TEST)
+            .union(CfgRegCM::BDU);
+        self.iface.write_mag_register(regc)?;
+        self.cfg_reg_c_m = regc;
+
+        let regb = CfgRegBM::OFF_CANC;
+        self.iface.write_mag_register(regb)?;
+        self.cfg_reg_b_m = regb;
+
+        // Temperature compensation must be enabled for the self-test.
+        let rega = CfgRegAM::COMP_TEMP_EN
+            .with_odr(MagOutputDataRate::Hz100)
+            .continuous_mode();
+        self.iface.write_mag_register(rega)?;
+        self.cfg_reg_a_m = rega;
+
+        delay.delay_us(MAG_SETTLING_TIME_US);
+        let no_st = self.mag_self_test_average(delay)?;
+
+        let regc = regc.union(CfgRegCM::SELF_TEST);
+        self.iface.write_mag_register(regc)?;
+        self.cfg_reg_c_m = regc;
+
+        delay.delay_us(MAG_ST_SETTLING_TIME_US);
+        let st = self.mag_self_test_average(delay)?;
+
+        Ok(delta(no_st, st))
+    }
+
+    fn mag_self_test_average<D: DelayUs<u32>>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(i32, i32, i32), Error<CommE, PinE>> {
+        // Discard the first sample.
+        self.mag_wait_for_data(delay)?;
+        self.iface
+            .read_mag_3_double_registers::<MagneticField>(self.cfg_reg_c_m.endianness())?;
+
+        let mut sum = (0, 0, 0);
+        for _ in 0..MAG_SAMPLES {
+            self.mag_wait_for_data(delay)?;
+            let (x, y, z) = self
+                .iface
+                .read_mag_3_double_registers::<MagneticField>(self.cfg_reg_c_m.endianness())?
+                .xyz_nt();
+            sum = (sum.0 + x, sum.1 + y, sum.2 + z);
+        }
+
+        Ok((
+            sum.0 / MAG_SAMPLES,
+            sum.1 / MAG_SAMPLES,
+            sum.2 / MAG_SAMPLES,
+        ))
+    }
+
+    fn mag_wait_for_data<D: DelayUs<u32>>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), Error<CommE, PinE>> {
+        for _ in 0..MAX_POLLS {
+            if self.mag_status()?.xyz_new_data() {
+                return Ok(());
+            }
+            delay.delay_us(POLL_INTERVAL_US);
+        }
+
+        Err(Error::Timeout)
+    }
+}
+
+fn delta(no_st: (i32, i32, i32), st: (i32, i32, i32)) -> (i32, i32, i32) {
+    (
+        (st.0 - no_st.0).abs(),
+        (st.1 - no_st.1).abs(),
+        (st.2 - no_st.2).abs(),
+    )
+}
@@ -1,16 +1,33 @@
 use bitflags::bitflags;
 
-use crate::register_address::{RegRead, StatusRegAuxA, WhoAmIA, WhoAmIM};
+use crate::{
+    register_address::{
+        ClickCfgA, ClickSrcA, FifoSrcRegA, Int1CfgA, IntCtrlRegM, IntSourceRegM, RegRead, RegWrite,
+        StatusRegAuxA, WhoAmIA, WhoAmIM,
+    },
+    Lsm303agr,
+};
 
 /// All possible errors in this crate
 #[derive(Debug)]
 pub enum Error<CommE, PinE> {
     /// I²C / SPI communication error
     Comm(CommE),
-    /// Chip-select pin error (SPI)
+    /// Chip-select pin error (SPI) or interrupt pin error (async)
     Pin(PinE),
     /// Invalid input data provided
     InvalidInputData,
+    /// The value read back after writing a register does not match
+    VerifyFailed {
+        /// Register address
+        register: u8,
+        /// Written value
+        expected: u8,
+        /// Value read back
+        actual: u8,
+    },
+    /// No new data was available in time
+    Timeout,
 }
 
 /// All possible errors in this crate
@@ -32,6 +49,17 @@ pub mod mode {
     pub enum MagContinuous {}
 }
 
+/// Device driver in either magnetometer mode
+///
+/// Returned when the magnetometer mode is read from the device.
+#[derive(Debug)]
+pub enum AnyMode<DI> {
+    /// Magnetometer in one-shot (single) mode
+    MagOneShot(Lsm303agr<DI, mode::MagOneShot>),
+    /// Magnetometer in continuous mode
+    MagContinuous(Lsm303agr<DI, mode::MagContinuous>),
+}
+
 /// An Accelerometer ID.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct AccelerometerId {
@@ -55,13 +83,14 @@ impl AccelerometerId {
 }
 
 /// An acceleration measurement.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub struct Acceleration {
     pub(crate) x: u16,
     pub(crate) y: u16,
     pub(crate) z: u16,
     pub(crate) mode: AccelMode,
     pub(crate) scale: AccelScale,
+    pub(crate) axes: AxisSet,
 }
 
 impl RegRead<(u16, u16, u16)> for Acceleration {
@@ -161,6 +190,209 @@ impl Acceleration {
             (z_unscaled as i32) * scaling_factor,
         )
     }
+
+    /// Accelerometer axes which were enabled for this measurement.
+    #[inline]
+    pub const fn axes(&self) -> AxisSet {
+        self.axes
+    }
+
+    /// Raw acceleration in X-direction, if the X-axis is enabled.
+    #[inline]
+    pub const fn enabled_x_raw(&self) -> Option<u16> {
+        if self.axes.x {
+            Some(self.x_raw())
+        } else {
+            None
+        }
+    }
+
+    /// Raw acceleration in Y-direction, if the Y-axis is enabled.
+    #[inline]
+    pub const fn enabled_y_raw(&self) -> Option<u16> {
+        if self.axes.y {
+            Some(self.y_raw())
+        } else {
+            None
+        }
+    }
+
+    /// Raw acceleration in Z-direction, if the Z-axis is enabled.
+    #[inline]
+    pub const fn enabled_z_raw(&self) -> Option<u16> {
+        if self.axes.z {
+            Some(self.z_raw())
+        } else {
+            None
+        }
+    }
+
+    /// Raw acceleration in X-, Y- and Z-directions, for each enabled axis.
+    #[inline]
+    pub const fn enabled_xyz_raw(&self) -> (Option<u16>, Option<u16>, Option<u16>) {
+        (
+            self.enabled_x_raw(),
+            self.enabled_y_raw(),
+            self.enabled_z_raw(),
+        )
+    }
+
+    /// Unscaled acceleration in X-direction, if the X-axis is enabled.
+    #[inline]
+    pub const fn enabled_x_unscaled(&self) -> Option<i16> {
+        if self.axes.x {
+            Some(self.x_unscaled())
+        } else {
+            None
+        }
+    }
+
+    /// Unscaled acceleration in Y-direction, if the Y-axis is enabled.
+    #[inline]
+    pub const fn enabled_y_unscaled(&self) -> Option<i16> {
+        if self.axes.y {
+            Some(self.y_unscaled())
+        } else {
+            None
+        }
+    }
+
+    /// Unscaled acceleration in Z-direction, if the Z-axis is enabled.
+    #[inline]
+    pub const fn enabled_z_unscaled(&self) -> Option<i16> {
+        if self.axes.z {
+            Some(self.z_unscaled())
+        } else {
+            None
+        }
+    }
+
+    /// Unscaled acceleration in X-, Y- and Z-directions, for each enabled axis.
+    #[inline]
+    pub const fn enabled_xyz_unscaled(&self) -> (Option<i16>, Option<i16>, Option<i16>) {
+        (
+            self.enabled_x_unscaled(),
+            self.enabled_y_unscaled(),
+            self.enabled_z_unscaled(),
+        )
+    }
+
+    /// Acceleration in X-direction in m*g* (milli-*g*), if the X-axis is enabled.
+    #[inline]
+    pub const fn enabled_x_mg(&self) -> Option<i32> {
+        if self.axes.x {
+            Some(self.x_mg())
+        } else {
+            None
+        }
+    }
+
+    /// Acceleration in Y-direction in m*g* (milli-*g*), if the Y-axis is enabled.
+    #[inline]
+    pub const fn enabled_y_mg(&self) -> Option<i32> {
+        if self.axes.y {
+            Some(self.y_mg())
+        } else {
+            None
+        }
+    }
+
+    /// Acceleration in Z-direction in m*g* (milli-*g*), if the Z-axis is enabled.
+    #[inline]
+    pub const fn enabled_z_mg(&self) -> Option<i32> {
+        if self.axes.z {
+            Some(self.z_mg())
+        } else {
+            None
+        }
+    }
+
+    /// Acceleration in X-, Y- and Z-directions in m*g* (milli-*g*), for each enabled axis.
+    #[inline]
+    pub const fn enabled_xyz_mg(&self) -> (Option<i32>, Option<i32>, Option<i32>) {
+        (
+            self.enabled_x_mg(),
+            self.enabled_y_mg(),
+            self.enabled_z_mg(),
+        )
+    }
+}
+
+/// Set of accelerometer axes
+///
+/// All axes are enabled by default.
+///
+/// ```
+/// use lsm303agr::AxisSet;
+///
+/// let axes = AxisSet::none().z(true);
+/// assert!(axes.contains_z());
+/// assert!(!axes.contains_x());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AxisSet {
+    x: bool,
+    y: bool,
+    z: bool,
+}
+
+impl Default for AxisSet {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl AxisSet {
+    /// Set containing the X-, Y- and Z-axes.
+    pub const fn all() -> Self {
+        Self {
+            x: true,
+            y: true,
+            z: true,
+        }
+    }
+
+    /// Set containing no axes.
+    pub const fn none() -> Self {
+        Self {
+            x: false,
+            y: false,
+            z: false,
+        }
+    }
+
+    /// Include/exclude the X-axis.
+    pub const fn x(self, x: bool) -> Self {
+        Self { x, ..self }
+    }
+
+    /// Include/exclude the Y-axis.
+    pub const fn y(self, y: bool) -> Self {
+        Self { y, ..self }
+    }
+
+    /// Include/exclude the Z-axis.
+    pub const fn z(self, z: bool) -> Self {
+        Self { z, ..self }
+    }
+
+    /// The X-axis is included.
+    #[inline]
+    pub const fn contains_x(&self) -> bool {
+        self.x
+    }
+
+    /// The Y-axis is included.
+    #[inline]
+    pub const fn contains_y(&self) -> bool {
+        self.y
+    }
+
+    /// The Z-axis is included.
+    #[inline]
+    pub const fn contains_z(&self) -> bool {
+        self.z
+    }
 }
 
 /// A Magnetometer ID.
@@ -281,6 +513,80 @@ impl MagneticField {
     }
 }
 
+/// Magnetometer hard-iron offset.
+///
+/// The offset is subtracted from the measured magnetic field by the device.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct HardIronOffset {
+    x: u16,
+    y: u16,
+    z: u16,
+}
+
+impl RegRead<(u16, u16, u16)> for HardIronOffset {
+    type Output = Self;
+
+    /// OFFSET_X_REG_L_M
+    const ADDR: u8 = 0x45;
+
+    #[inline(always)]
+    fn from_data((x, y, z): (u16, u16, u16)) -> Self::Output {
+        Self { x, y, z }
+    }
+}
+
+impl RegWrite<(u16, u16, u16)> for HardIronOffset {
+    #[inline(always)]
+    fn data(&self) -> (u16, u16, u16) {
+        (self.x, self.y, self.z)
+    }
+}
+
+impl HardIronOffset {
+    /// Create an offset from unscaled values in X-, Y- and Z-directions.
+    pub const fn from_unscaled(x: i16, y: i16, z: i16) -> Self {
+        Self {
+            x: x as u16,
+            y: y as u16,
+            z: z as u16,
+        }
+    }
+
+    /// Create an offset from values in X-, Y- and Z-directions in nT (nano-Tesla).
+    ///
+    /// The values are clamped to the register range.
+    pub const fn from_nt(x: i32, y: i32, z: i32) -> Self {
+        const fn unscaled(nt: i32) -> i16 {
+            let value = nt / MagneticField::SCALING_FACTOR;
+            if value > i16::MAX as i32 {
+                i16::MAX
+            } else if value < i16::MIN as i32 {
+                i16::MIN
+            } else {
+                value as i16
+            }
+        }
+
+        Self::from_unscaled(unscaled(x), unscaled(y), unscaled(z))
+    }
+
+    /// Unscaled offset in X-, Y- and Z-directions.
+    #[inline]
+    pub const fn xyz_unscaled(&self) -> (i16, i16, i16) {
+        (self.x as i16, self.y as i16, self.z as i16)
+    }
+
+    /// Offset in X-, Y- and Z-directions in nT (nano-Tesla).
+    #[inline]
+    pub const fn xyz_nt(&self) -> (i32, i32, i32) {
+        (
+            (self.x as i16 as i32) * MagneticField::SCALING_FACTOR,
+            (self.y as i16 as i32) * MagneticField::SCALING_FACTOR,
+            (self.z as i16 as i32) * MagneticField::SCALING_FACTOR,
+        )
+    }
+}
+
 /// Accelerometer output data rate
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AccelOutputDataRate {
@@ -324,6 +630,32 @@ impl AccelOutputDataRate {
         })
     }
 
+    /// Frequency in Hertz.
+    pub(crate) const fn hertz(&self) -> u32 {
+        match self {
+            Self::Hz1 => 1,
+            Self::Hz10 => 10,
+            Self::Hz25 => 25,
+            Self::Hz50 => 50,
+            Self::Hz100 => 100,
+            Self::Hz200 => 200,
+            Self::Hz400 => 400,
+            Self::Khz1_344 => 1344,
+            Self::Khz1_620LowPower => 1620,
+            Self::Khz5_376LowPower => 5376,
+        }
+    }
+
+    /// Number of 1/ODR periods elapsed in the given time.
+    pub(crate) const fn periods_from_ms(&self, ms: u32) -> u32 {
+        let periods = ms as u64 * self.hertz() as u64 / 1000;
+        if periods > u32::MAX as u64 {
+            u32::MAX
+        } else {
+            periods as u32
+        }
+    }
+
     /// 1/ODR ms
     pub(crate) const fn turn_on_time_us_frac_1(&self) -> u32 {
         match self {
@@ -370,6 +702,13 @@ pub enum AccelMode {
     HighResolution,
 }
 
+#[allow(clippy::derivable_impls)] // `#[default]` requires Rust 1.62.
+impl Default for AccelMode {
+    fn default() -> Self {
+        Self::PowerDown
+    }
+}
+
 impl AccelMode {
     pub(crate) const fn turn_on_time_us(&self, odr: AccelOutputDataRate) -> u32 {
         match self {
@@ -425,6 +764,35 @@ pub enum AccelScale {
     G16 = 16,
 }
 
+#[allow(clippy::derivable_impls)] // `#[default]` requires Rust 1.62.
+impl Default for AccelScale {
+    fn default() -> Self {
+        Self::G2
+    }
+}
+
+impl AccelScale {
+    /// Threshold register LSB in m*g*.
+    const fn threshold_lsb_mg(&self) -> u16 {
+        match self {
+            Self::G2 => 16,
+            Self::G4 => 32,
+            Self::G8 => 62,
+            Self::G16 => 186,
+        }
+    }
+
+    /// 7-bit threshold register value for the given m*g*.
+    pub(crate) const fn threshold_from_mg(&self, mg: u16) -> u8 {
+        let threshold = mg / self.threshold_lsb_mg();
+        if threshold > 0x7F {
+            0x7F
+        } else {
+            threshold as u8
+        }
+    }
+}
+
 /// Magnetometer output data rate
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MagOutputDataRate {
@@ -470,6 +838,7 @@ pub enum MagMode {
     HighResolution,
 }
 
+#[allow(clippy::derivable_impls)] // `#[default]` requires Rust 1.62.
 impl Default for MagMode {
     fn default() -> Self {
         Self::HighResolution
@@ -636,21 +1005,787 @@ pub enum FifoMode {
     StreamToFifo,
 }
 
+/// Accelerometer FIFO status
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct FifoStatus {
+    flags: FifoSrcRegA,
+}
+
+impl FifoStatus {
+    pub(crate) const fn new(flags: FifoSrcRegA) -> Self {
+        Self { flags }
+    }
+
+    /// FIFO content exceeds the watermark level.
+    #[inline]
+    pub const fn watermark(&self) -> bool {
+        self.flags.contains(FifoSrcRegA::WTM)
+    }
+
+    /// FIFO is completely filled and at least one sample has been overwritten.
+    #[inline]
+    pub const fn overrun(&self) -> bool {
+        self.flags.contains(FifoSrcRegA::OVRN_FIFO)
+    }
+
+    /// FIFO is empty.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.flags.contains(FifoSrcRegA::EMPTY)
+    }
+
+    /// Number of unread samples stored in the FIFO.
+    #[inline]
+    pub const fn len(&self) -> u8 {
+        if self.overrun() {
+            FifoSrcRegA::CAPACITY
+        } else {
+            self.flags.intersection(FifoSrcRegA::FSS).bits()
+        }
+    }
+}
+
+/// Accelerometer high-pass filter mode
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HighPassFilterMode {
+    /// Normal mode, reset by reading the REFERENCE register
+    NormalWithReset,
+    /// Reference signal for filtering
+    Reference,
+    /// Normal mode
+    Normal,
+    /// Autoreset on interrupt event
+    AutoResetOnInterrupt,
+}
+
+/// Accelerometer high-pass filter cut-off frequency
+///
+/// The actual frequency depends on the output data rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HighPassFilterCutoff {
+    /// Highest cut-off frequency (HPCF = 00)
+    Highest,
+    /// High cut-off frequency (HPCF = 01)
+    High,
+    /// Low cut-off frequency (HPCF = 10)
+    Low,
+    /// Lowest cut-off frequency (HPCF = 11)
+    Lowest,
+}
+
+/// Accelerometer high-pass filter target
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HighPassFilterTarget {
+    /// Output data and FIFO
+    OutputData,
+    /// Click detection
+    Click,
+    /// Inertial interrupt generator 1 (AOI1)
+    Aoi1,
+    /// Inertial interrupt generator 2 (AOI2)
+    Aoi2,
+}
+
 /// An interrupt.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Interrupt {
-    /// AOI1 interrupt on INT1 pin.
+    /// AOI1 interrupt.
     Aoi1,
-    /// AOI2 interrupt on INT1 pin.
+    /// AOI2 interrupt.
     Aoi2,
-    /// CLICK interrupt on INT1 pin.
+    /// CLICK interrupt.
     Click,
-    /// DRDY1 interrupt on INT1 pin.
+    /// DRDY1 interrupt (INT1 pin only).
     DataReady1,
-    /// DRDY2 interrupt on INT1 pin.
+    /// DRDY2 interrupt (INT1 pin only).
     DataReady2,
-    /// FIFO overrun interrupt on INT1 pin.
+    /// FIFO overrun interrupt (INT1 pin only).
     FifoOverrun,
-    /// FIFO watermark interrupt on INT1 pin.
+    /// FIFO watermark interrupt (INT1 pin only).
     FifoWatermark,
 }
+
+/// Accelerometer interrupt pin
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InterruptPin {
+    /// INT1 pin
+    Int1,
+    /// INT2 pin
+    Int2,
+}
+
+/// Accelerometer interrupt pin polarity
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InterruptPolarity {
+    /// Interrupt pins are active-high (default).
+    ActiveHigh,
+    /// Interrupt pins are active-low.
+    ActiveLow,
+}
+
+#[allow(clippy::derivable_impls)] // `#[default]` requires Rust 1.62.
+impl Default for InterruptPolarity {
+    fn default() -> Self {
+        Self::ActiveHigh
+    }
+}
+
+/// Byte order of the output data registers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Endianness {
+    /// Low byte at the lower address (default).
+    Little,
+    /// High byte at the lower address.
+    Big,
+}
+
+#[allow(clippy::derivable_impls)] // `#[default]` requires Rust 1.62.
+impl Default for Endianness {
+    fn default() -> Self {
+        Self::Little
+    }
+}
+
+/// Combination of accelerometer inertial interrupt events.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InterruptMode {
+    /// OR combination of the enabled events.
+    Or,
+    /// AND combination of the enabled events.
+    And,
+    /// 6-direction movement recognition.
+    Movement6D,
+    /// 6-direction position recognition.
+    Position6D,
+}
+
+#[allow(clippy::derivable_impls)] // `#[default]` requires Rust 1.62.
+impl Default for InterruptMode {
+    fn default() -> Self {
+        Self::Or
+    }
+}
+
+/// Accelerometer inertial interrupt generator configuration.
+///
+/// The threshold is converted into register units using the accelerometer
+/// scale and the duration using the output data rate active at the time the
+/// configuration is written.
+///
+/// ```
+/// use lsm303agr::{InterruptConfig, InterruptMode};
+///
+/// let config = InterruptConfig::default()
+///     .mode(InterruptMode::Or)
+///     .x_high(true)
+///     .y_high(true)
+///     .threshold_mg(250)
+///     .duration_ms(100);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct InterruptConfig {
+    mode: InterruptMode,
+    x_high: bool,
+    x_low: bool,
+    y_high: bool,
+    y_low: bool,
+    z_high: bool,
+    z_low: bool,
+    pub(crate) threshold_mg: u16,
+    pub(crate) duration_ms: u16,
+}
+
+impl InterruptConfig {
+    /// Set the combination of interrupt events.
+    pub const fn mode(self, mode: InterruptMode) -> Self {
+        Self { mode, ..self }
+    }
+
+    /// Enable/disable interrupt on X-axis high event or direction recognition.
+    pub const fn x_high(self, x_high: bool) -> Self {
+        Self { x_high, ..self }
+    }
+
+    /// Enable/disable interrupt on X-axis low event or direction recognition.
+    pub const fn x_low(self, x_low: bool) -> Self {
+        Self { x_low, ..self }
+    }
+
+    /// Enable/disable interrupt on Y-axis high event or direction recognition.
+    pub const fn y_high(self, y_high: bool) -> Self {
+        Self { y_high, ..self }
+    }
+
+    /// Enable/disable interrupt on Y-axis low event or direction recognition.
+    pub const fn y_low(self, y_low: bool) -> Self {
+        Self { y_low, ..self }
+    }
+
+    /// Enable/disable interrupt on Z-axis high event or direction recognition.
+    pub const fn z_high(self, z_high: bool) -> Self {
+        Self { z_high, ..self }
+    }
+
+    /// Enable/disable interrupt on Z-axis low event or direction recognition.
+    pub const fn z_low(self, z_low: bool) -> Self {
+        Self { z_low, ..self }
+    }
+
+    /// Set the interrupt threshold in m*g* (milli-*g*).
+    ///
+    /// The register value is clamped to its maximum.
+    pub const fn threshold_mg(self, threshold_mg: u16) -> Self {
+        Self {
+            threshold_mg,
+            ..self
+        }
+    }
+
+    /// Set the minimum duration of the interrupt event in milliseconds.
+    ///
+    /// The register value is clamped to its maximum.
+    pub const fn duration_ms(self, duration_ms: u16) -> Self {
+        Self {
+            duration_ms,
+            ..self
+        }
+    }
+
+    pub(crate) const fn cfg_bits(&self) -> u8 {
+        let mut cfg = match self.mode {
+            InterruptMode::Or => Int1CfgA::empty(),
+            InterruptMode::And => Int1CfgA::AOI,
+            InterruptMode::Movement6D => Int1CfgA::D6,
+            InterruptMode::Position6D => Int1CfgA::AOI.union(Int1CfgA::D6),
+        };
+
+        if self.x_high {
+            cfg = cfg.union(Int1CfgA::XHIE);
+        }
+        if self.x_low {
+            cfg = cfg.union(Int1CfgA::XLIE);
+        }
+        if self.y_high {
+            cfg = cfg.union(Int1CfgA::YHIE);
+        }
+        if self.y_low {
+            cfg = cfg.union(Int1CfgA::YLIE);
+        }
+        if self.z_high {
+            cfg = cfg.union(Int1CfgA::ZHIE);
+        }
+        if self.z_low {
+            cfg = cfg.union(Int1CfgA::ZLIE);
+        }
+
+        cfg.bits()
+    }
+}
+
+bitflags! {
+    #[derive(Default)]
+    pub struct InterruptSourceFlags: u8 {
+        const IA = 0b01000000;
+        const ZH = 0b00100000;
+        const ZL = 0b00010000;
+        const YH = 0b00001000;
+        const YL = 0b00000100;
+        const XH = 0b00000010;
+        const XL = 0b00000001;
+    }
+}
+
+/// Accelerometer inertial interrupt source
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct InterruptSource {
+    flags: InterruptSourceFlags,
+}
+
+impl InterruptSource {
+    pub(crate) const fn new(flags: InterruptSourceFlags) -> Self {
+        Self { flags }
+    }
+
+    /// One or more interrupts have been generated.
+    #[inline]
+    pub const fn active(&self) -> bool {
+        self.flags.contains(InterruptSourceFlags::IA)
+    }
+
+    /// X-axis high event has occurred.
+    #[inline]
+    pub const fn x_high(&self) -> bool {
+        self.flags.contains(InterruptSourceFlags::XH)
+    }
+
+    /// X-axis low event has occurred.
+    #[inline]
+    pub const fn x_low(&self) -> bool {
+        self.flags.contains(InterruptSourceFlags::XL)
+    }
+
+    /// Y-axis high event has occurred.
+    #[inline]
+    pub const fn y_high(&self) -> bool {
+        self.flags.contains(InterruptSourceFlags::YH)
+    }
+
+    /// Y-axis low event has occurred.
+    #[inline]
+    pub const fn y_low(&self) -> bool {
+        self.flags.contains(InterruptSourceFlags::YL)
+    }
+
+    /// Z-axis high event has occurred.
+    #[inline]
+    pub const fn z_high(&self) -> bool {
+        self.flags.contains(InterruptSourceFlags::ZH)
+    }
+
+    /// Z-axis low event has occurred.
+    #[inline]
+    pub const fn z_low(&self) -> bool {
+        self.flags.contains(InterruptSourceFlags::ZL)
+    }
+}
+
+/// Accelerometer click (tap) detection configuration.
+///
+/// The threshold is converted into register units using the accelerometer
+/// scale and the time limit, latency and window using the output data rate
+/// active at the time the configuration is written.
+///
+/// ```
+/// use lsm303agr::ClickConfig;
+///
+/// let config = ClickConfig::default()
+///     .z_single(true)
+///     .z_double(true)
+///     .threshold_mg(500)
+///     .time_limit_ms(50)
+///     .time_latency_ms(100)
+///     .time_window_ms(300);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ClickConfig {
+    x_single: bool,
+    x_double: bool,
+    y_single: bool,
+    y_double: bool,
+    z_single: bool,
+    z_double: bool,
+    pub(crate) threshold_mg: u16,
+    pub(crate) time_limit_ms: u16,
+    pub(crate) time_latency_ms: u16,
+    pub(crate) time_window_ms: u16,
+    pub(crate) latched: bool,
+}
+
+impl ClickConfig {
+    /// Enable/disable single-click detection on the X-axis.
+    pub const fn x_single(self, x_single: bool) -> Self {
+        Self { x_single, ..self }
+    }
+
+    /// Enable/disable double-click detection on the X-axis.
+    pub const fn x_double(self, x_double: bool) -> Self {
+        Self { x_double, ..self }
+    }
+
+    /// Enable/disable single-click detection on the Y-axis.
+    pub const fn y_single(self, y_single: bool) -> Self {
+        Self { y_single, ..self }
+    }
+
+    /// Enable/disable double-click detection on the Y-axis.
+    pub const fn y_double(self, y_double: bool) -> Self {
+        Self { y_double, ..self }
+    }
+
+    /// Enable/disable single-click detection on the Z-axis.
+    pub const fn z_single(self, z_single: bool) -> Self {
+        Self { z_single, ..self }
+    }
+
+    /// Enable/disable double-click detection on the Z-axis.
+    pub const fn z_double(self, z_double: bool) -> Self {
+        Self { z_double, ..self }
+    }
+
+    /// Set the click threshold in m*g* (milli-*g*).
+    ///
+    /// The register value is clamped to its maximum.
+    pub const fn threshold_mg(self, threshold_mg: u16) -> Self {
+        Self {
+            threshold_mg,
+            ..self
+        }
+    }
+
+    /// Set the maximum time in milliseconds the acceleration may stay above
+    /// the threshold for a click to be detected.
+    ///
+    /// The register value is clamped to its maximum.
+    pub const fn time_limit_ms(self, time_limit_ms: u16) -> Self {
+        Self {
+            time_limit_ms,
+            ..self
+        }
+    }
+
+    /// Set the time in milliseconds after the first click during which
+    /// clicks are ignored when detecting a double click.
+    ///
+    /// The register value is clamped to its maximum.
+    pub const fn time_latency_ms(self, time_latency_ms: u16) -> Self {
+        Self {
+            time_latency_ms,
+            ..self
+        }
+    }
+
+    /// Set the time window in milliseconds after the latency during which
+    /// the second click of a double click must start.
+    ///
+    /// The register value is clamped to its maximum.
+    pub const fn time_window_ms(self, time_window_ms: u16) -> Self {
+        Self {
+            time_window_ms,
+            ..self
+        }
+    }
+
+    /// Enable/disable latching the click interrupt until the source is read.
+    pub const fn latched(self, latched: bool) -> Self {
+        Self { latched, ..self }
+    }
+
+    pub(crate) const fn cfg(&self) -> ClickCfgA {
+        let mut cfg = ClickCfgA::empty();
+
+        if self.x_single {
+            cfg = cfg.union(ClickCfgA::XS);
+        }
+        if self.x_double {
+            cfg = cfg.union(ClickCfgA::XD);
+        }
+        if self.y_single {
+            cfg = cfg.union(ClickCfgA::YS);
+        }
+        if self.y_double {
+            cfg = cfg.union(ClickCfgA::YD);
+        }
+        if self.z_single {
+            cfg = cfg.union(ClickCfgA::ZS);
+        }
+        if self.z_double {
+            cfg = cfg.union(ClickCfgA::ZD);
+        }
+
+        cfg
+    }
+}
+
+/// Accelerometer click (tap) source
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ClickSource {
+    flags: ClickSrcA,
+}
+
+impl ClickSource {
+    pub(crate) const fn new(flags: ClickSrcA) -> Self {
+        Self { flags }
+    }
+
+    /// One or more click interrupts have been generated.
+    #[inline]
+    pub const fn active(&self) -> bool {
+        self.flags.contains(ClickSrcA::IA)
+    }
+
+    /// A single click has been detected.
+    #[inline]
+    pub const fn single_click(&self) -> bool {
+        self.flags.contains(ClickSrcA::SCLICK)
+    }
+
+    /// A double click has been detected.
+    #[inline]
+    pub const fn double_click(&self) -> bool {
+        self.flags.contains(ClickSrcA::DCLICK)
+    }
+
+    /// The click was detected with negative sign.
+    #[inline]
+    pub const fn negative(&self) -> bool {
+        self.flags.contains(ClickSrcA::SIGN)
+    }
+
+    /// The click was detected on the X-axis.
+    #[inline]
+    pub const fn x(&self) -> bool {
+        self.flags.contains(ClickSrcA::X)
+    }
+
+    /// The click was detected on the Y-axis.
+    #[inline]
+    pub const fn y(&self) -> bool {
+        self.flags.contains(ClickSrcA::Y)
+    }
+
+    /// The click was detected on the Z-axis.
+    #[inline]
+    pub const fn z(&self) -> bool {
+        self.flags.contains(ClickSrcA::Z)
+    }
+}
+
+/// Magnetometer threshold interrupt configuration.
+///
+/// The interrupt generator is enabled if at least one axis is enabled.
+///
+/// ```
+/// use lsm303agr::{InterruptPolarity, MagInterruptConfig};
+///
+/// let config = MagInterruptConfig::default()
+///     .z(true)
+///     .polarity(InterruptPolarity::ActiveHigh)
+///     .latched(true)
+///     .threshold_nt(30_000);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct MagInterruptConfig {
+    x: bool,
+    y: bool,
+    z: bool,
+    polarity: InterruptPolarity,
+    latched: bool,
+    threshold_nt: u32,
+}
+
+impl MagInterruptConfig {
+    /// Enable/disable interrupt recognition on the X-axis.
+    pub const fn x(self, x: bool) -> Self {
+        Self { x, ..self }
+    }
+
+    /// Enable/disable interrupt recognition on the Y-axis.
+    pub const fn y(self, y: bool) -> Self {
+        Self { y, ..self }
+    }
+
+    /// Enable/disable interrupt recognition on the Z-axis.
+    pub const fn z(self, z: bool) -> Self {
+        Self { z, ..self }
+    }
+
+    /// Set the interrupt polarity.
+    pub const fn polarity(self, polarity: InterruptPolarity) -> Self {
+        Self { polarity, ..self }
+    }
+
+    /// Latch the interrupt until the source is read.
+    pub const fn latched(self, latched: bool) -> Self {
+        Self { latched, ..self }
+    }
+
+    /// Set the interrupt threshold in nT (nano-Tesla).
+    ///
+    /// The threshold applies to both positive and negative values.
+    /// The register value is clamped to its maximum.
+    pub const fn threshold_nt(self, threshold_nt: u32) -> Self {
+        Self {
+            threshold_nt,
+            ..self
+        }
+    }
+
+    pub(crate) const fn threshold(&self) -> u16 {
+        let threshold = self.threshold_nt / MagneticField::SCALING_FACTOR as u32;
+        if threshold > u16::MAX as u32 {
+            u16::MAX
+        } else {
+            threshold as u16
+        }
+    }
+
+    pub(crate) const fn ctrl(&self) -> IntCtrlRegM {
+        let mut ctrl = IntCtrlRegM::empty();
+
+        if self.x {
+            ctrl = ctrl.union(IntCtrlRegM::XIEN);
+        }
+        if self.y {
+            ctrl = ctrl.union(IntCtrlRegM::YIEN);
+        }
+        if self.z {
+            ctrl = ctrl.union(IntCtrlRegM::ZIEN);
+        }
+        if !ctrl.is_empty() {
+            ctrl = ctrl.union(IntCtrlRegM::IEN);
+        }
+        if let InterruptPolarity::ActiveHigh = self.polarity {
+            ctrl = ctrl.union(IntCtrlRegM::IEA);
+        }
+        if self.latched {
+            ctrl = ctrl.union(IntCtrlRegM::IEL);
+        }
+
+        ctrl
+    }
+}
+
+/// Magnetometer data checked by the threshold interrupt
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MagInterruptDataSource {
+    /// Measured data before hard-iron correction (default).
+    Uncorrected,
+    /// Measured data after hard-iron correction.
+    HardIronCorrected,
+}
+
+#[allow(clippy::derivable_impls)] // `#[default]` requires Rust 1.62.
+impl Default for MagInterruptDataSource {
+    fn default() -> Self {
+        Self::Uncorrected
+    }
+}
+
+/// Magnetometer set pulse frequency
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MagSetPulseFrequency {
+    /// Set pulse every 63 output data rate cycles (default).
+    Every63Odr,
+    /// Set pulse only at power-on, after the power-down condition.
+    PowerOnOnly,
+}
+
+#[allow(clippy::derivable_impls)] // `#[default]` requires Rust 1.62.
+impl Default for MagSetPulseFrequency {
+    fn default() -> Self {
+        Self::Every63Odr
+    }
+}
+
+/// Magnetometer threshold interrupt source
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct MagInterruptSource {
+    flags: IntSourceRegM,
+}
+
+impl MagInterruptSource {
+    pub(crate) const fn new(flags: IntSourceRegM) -> Self {
+        Self { flags }
+    }
+
+    /// The interrupt is signalled.
+    ///
+    /// The level of this flag follows the configured interrupt polarity.
+    #[inline]
+    pub const fn interrupt(&self) -> bool {
+        self.flags.contains(IntSourceRegM::INT)
+    }
+
+    /// The X-axis value exceeded the threshold on the positive side.
+    #[inline]
+    pub const fn x_positive(&self) -> bool {
+        self.flags.contains(IntSourceRegM::P_TH_S_X)
+    }
+
+    /// The Y-axis value exceeded the threshold on the positive side.
+    #[inline]
+    pub const fn y_positive(&self) -> bool {
+        self.flags.contains(IntSourceRegM::P_TH_S_Y)
+    }
+
+    /// The Z-axis value exceeded the threshold on the positive side.
+    #[inline]
+    pub const fn z_positive(&self) -> bool {
+        self.flags.contains(IntSourceRegM::P_TH_S_Z)
+    }
+
+    /// The X-axis value exceeded the threshold on the negative side.
+    #[inline]
+    pub const fn x_negative(&self) -> bool {
+        self.flags.contains(IntSourceRegM::N_TH_S_X)
+    }
+
+    /// The Y-axis value exceeded the threshold on the negative side.
+    #[inline]
+    pub const fn y_negative(&self) -> bool {
+        self.flags.contains(IntSourceRegM::N_TH_S_Y)
+    }
+
+    /// The Z-axis value exceeded the threshold on the negative side.
+    #[inline]
+    pub const fn z_negative(&self) -> bool {
+        self.flags.contains(IntSourceRegM::N_TH_S_Z)
+    }
+
+    /// The internal measurement range overflowed.
+    #[inline]
+    pub const fn overflow(&self) -> bool {
+        self.flags.contains(IntSourceRegM::MROI)
+    }
+}
+
+/// Self-test result.
+///
+/// Holds the absolute per-axis difference between the output with and without
+/// self-test enabled, together with the datasheet limits it is checked against.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SelfTestResult {
+    delta: (i32, i32, i32),
+    min: i32,
+    max: i32,
+}
+
+impl SelfTestResult {
+    pub(crate) const fn new(delta: (i32, i32, i32), min: i32, max: i32) -> Self {
+        Self { delta, min, max }
+    }
+
+    const fn in_limits(&self, delta: i32) -> bool {
+        delta >= self.min && delta <= self.max
+    }
+
+    /// Absolute per-axis output change in X-, Y- and Z-directions.
+    ///
+    /// The unit is m*g* (milli-*g*) for the accelerometer and nT (nano-Tesla)
+    /// for the magnetometer.
+    #[inline]
+    pub const fn xyz_delta(&self) -> (i32, i32, i32) {
+        self.delta
+    }
+
+    /// Minimum and maximum allowed output change, in the same unit as
+    /// [`xyz_delta()`](SelfTestResult::xyz_delta).
+    #[inline]
+    pub const fn limits(&self) -> (i32, i32) {
+        (self.min, self.max)
+    }
+
+    /// The X-axis output change is within the limits.
+    #[inline]
+    pub const fn x_passed(&self) -> bool {
+        self.in_limits(self.delta.0)
+    }
+
+    /// The Y-axis output change is within the limits.
+    #[inline]
+    pub const fn y_passed(&self) -> bool {
+        self.in_limits(self.delta.1)
+    }
+
+    /// The Z-axis output change is within the limits.
+    #[inline]
+    pub const fn z_passed(&self) -> bool {
+        self.in_limits(self.delta.2)
+    }
+
+    /// The output change of all axes is within the limits.
+    #[inline]
+    pub const fn passed(&self) -> bool {
+        self.x_passed() && self.y_passed() && self.z_passed()
+    }
+}
@@ -0,0 +1,296 @@
+mod common;
+use crate::common::{destroy_i2c, new_i2c, Register, ACCEL_ADDR, DEFAULT_CTRL_REG1_A};
+use embedded_hal_mock::{delay::MockNoop as Delay, i2c::Transaction as I2cTrans};
+use lsm303agr::{
+    AccelMode, AccelOutputDataRate as ODR, AccelScale, ClickConfig, InterruptConfig, InterruptMode,
+};
+
+#[test]
+fn can_configure_int1() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, 5 << 4 | DEFAULT_CTRL_REG1_A],
+        ),
+        // 250 mg / 16 mg
+        I2cTrans::write(ACCEL_ADDR, vec![Register::INT1_THS_A, 15]),
+        // 100 ms * 100 Hz
+        I2cTrans::write(ACCEL_ADDR, vec![Register::INT1_DURATION_A, 10]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::INT1_CFG_A, 0b00001010]),
+    ]);
+    sensor
+        .set_accel_mode_and_odr(&mut Delay, AccelMode::Normal, ODR::Hz100)
+        .unwrap();
+    sensor
+        .acc_configure_int1(
+            InterruptConfig::default()
+                .x_high(true)
+                .y_high(true)
+                .threshold_mg(250)
+                .duration_ms(100),
+        )
+        .unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_configure_int2_with_clamping() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0b00110000]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0b00110000]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, 7 << 4 | DEFAULT_CTRL_REG1_A],
+        ),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::INT2_THS_A, 0x7F]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::INT2_DURATION_A, 0x7F]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::INT2_CFG_A, 0b11010101]),
+    ]);
+    sensor.set_accel_scale(AccelScale::G16).unwrap();
+    sensor
+        .set_accel_mode_and_odr(&mut Delay, AccelMode::Normal, ODR::Hz400)
+        .unwrap();
+    sensor
+        .acc_configure_int2(
+            InterruptConfig::default()
+                .mode(InterruptMode::Position6D)
+                .x_low(true)
+                .y_low(true)
+                .z_low(true)
+                .threshold_mg(30000)
+                .duration_ms(1000),
+        )
+        .unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn cannot_configure_duration_when_powered_down() {
+    let mut sensor = new_i2c(&[]);
+    sensor
+        .acc_configure_int1(InterruptConfig::default().duration_ms(10))
+        .expect_err("should have returned error");
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_get_int_sources() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::INT1_SRC_A], vec![0b01000110]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::INT2_SRC_A], vec![0b00110001]),
+    ]);
+
+    let source = sensor.acc_int1_source().unwrap();
+    assert!(source.active());
+    assert!(source.x_high());
+    assert!(source.y_low());
+    assert!(!source.x_low());
+    assert!(!source.y_high());
+    assert!(!source.z_high());
+    assert!(!source.z_low());
+
+    let source = sensor.acc_int2_source().unwrap();
+    assert!(!source.active());
+    assert!(source.x_low());
+    assert!(source.z_low());
+    assert!(source.z_high());
+    assert!(!source.x_high());
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_take_latched_int_sources() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::INT1_SRC_A], vec![0b01000010]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::INT2_SRC_A], vec![0]),
+    ]);
+
+    let source = sensor.acc_take_int1_source().unwrap().unwrap();
+    assert!(source.x_high());
+    assert!(!source.x_low());
+
+    assert_eq!(sensor.acc_take_int2_source().unwrap(), None);
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_set_latched_and_4d() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG5_A, 0b00001000]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG5_A, 0b00001010]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG5_A, 0b00001110]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG5_A, 0b00001111]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG5_A, 0b00000111]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG5_A, 0b00000110]),
+    ]);
+    sensor.acc_set_int1_latched(true).unwrap();
+    sensor.acc_set_int2_latched(true).unwrap();
+    sensor.acc_set_int1_4d(true).unwrap();
+    sensor.acc_set_int2_4d(true).unwrap();
+    sensor.acc_set_int1_latched(false).unwrap();
+    sensor.acc_set_int2_4d(false).unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_enable_free_fall_detection() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, 5 << 4 | DEFAULT_CTRL_REG1_A],
+        ),
+        // 350 mg / 16 mg
+        I2cTrans::write(ACCEL_ADDR, vec![Register::INT1_THS_A, 21]),
+        // 30 ms * 100 Hz
+        I2cTrans::write(ACCEL_ADDR, vec![Register::INT1_DURATION_A, 3]),
+        // AND of all low events
+        I2cTrans::write(ACCEL_ADDR, vec![Register::INT1_CFG_A, 0b10010101]),
+        // I1_AOI1
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG3_A, 0b01000000]),
+    ]);
+    sensor
+        .set_accel_mode_and_odr(&mut Delay, AccelMode::Normal, ODR::Hz100)
+        .unwrap();
+    sensor.acc_enable_free_fall_detection(350, 30).unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_configure_click() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, 7 << 4 | DEFAULT_CTRL_REG1_A],
+        ),
+        // Latched, 500 mg / 16 mg
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CLICK_THS_A, 0x80 | 31]),
+        // 50 ms * 400 Hz
+        I2cTrans::write(ACCEL_ADDR, vec![Register::TIME_LIMIT_A, 20]),
+        // 100 ms * 400 Hz
+        I2cTrans::write(ACCEL_ADDR, vec![Register::TIME_LATENCY_A, 40]),
+        // 1000 ms * 400 Hz, clamped
+        I2cTrans::write(ACCEL_ADDR, vec![Register::TIME_WINDOW_A, 0xFF]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CLICK_CFG_A, 0b00110001]),
+    ]);
+    sensor
+        .set_accel_mode_and_odr(&mut Delay, AccelMode::Normal, ODR::Hz400)
+        .unwrap();
+    sensor
+        .acc_configure_click(
+            ClickConfig::default()
+                .x_single(true)
+                .z_single(true)
+                .z_double(true)
+                .threshold_mg(500)
+                .time_limit_ms(50)
+                .time_latency_ms(100)
+                .time_window_ms(1000)
+                .latched(true),
+        )
+        .unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_get_click_source() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CLICK_SRC_A], vec![0b01101100]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CLICK_SRC_A], vec![0b01010001]),
+    ]);
+
+    let source = sensor.acc_click_source().unwrap();
+    assert!(source.active());
+    assert!(source.double_click());
+    assert!(!source.single_click());
+    assert!(source.negative());
+    assert!(source.z());
+    assert!(!source.x());
+    assert!(!source.y());
+
+    let source = sensor.acc_click_source().unwrap();
+    assert!(source.active());
+    assert!(!source.double_click());
+    assert!(source.single_click());
+    assert!(!source.negative());
+    assert!(source.x());
+    assert!(!source.z());
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_enable_disable_activity_detection() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, 5 << 4 | DEFAULT_CTRL_REG1_A],
+        ),
+        // (2 s * 100 Hz - 1) / 8, rounded
+        I2cTrans::write(ACCEL_ADDR, vec![Register::ACT_DUR_A, 25]),
+        // 100 mg / 16 mg
+        I2cTrans::write(ACCEL_ADDR, vec![Register::ACT_THS_A, 6]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG6_A, 0b00001000]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::ACT_THS_A, 0]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG6_A, 0]),
+    ]);
+    sensor
+        .set_accel_mode_and_odr(&mut Delay, AccelMode::Normal, ODR::Hz100)
+        .unwrap();
+    sensor.acc_enable_activity_detection(100, 2).unwrap();
+    sensor.acc_enable_activity_interrupt().unwrap();
+    sensor.acc_disable_activity_detection().unwrap();
+    sensor.acc_disable_activity_interrupt().unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_enable_activity_detection_with_clamping() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, 1 << 4 | DEFAULT_CTRL_REG1_A],
+        ),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::ACT_DUR_A, 255]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::ACT_THS_A, 6]),
+    ]);
+    sensor
+        .set_accel_mode_and_odr(&mut Delay, AccelMode::Normal, ODR::Hz1)
+        .unwrap();
+    sensor.acc_enable_activity_detection(100, u16::MAX).unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_enable_activity_detection_at_lowest_odr() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, 1 << 4 | DEFAULT_CTRL_REG1_A],
+        ),
+        // 6 s is closer to (8 * 1 + 1) / 1 Hz than to 1 / 1 Hz.
+        I2cTrans::write(ACCEL_ADDR, vec![Register::ACT_DUR_A, 1]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::ACT_THS_A, 6]),
+    ]);
+    sensor
+        .set_accel_mode_and_odr(&mut Delay, AccelMode::Normal, ODR::Hz1)
+        .unwrap();
+    sensor.acc_enable_activity_detection(100, 6).unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn cannot_enable_activity_detection_when_powered_down() {
+    let mut sensor = new_i2c(&[]);
+    sensor
+        .acc_enable_activity_detection(100, 2)
+        .expect_err("should have returned error");
+    destroy_i2c(sensor);
+}
@@ -3,7 +3,10 @@ use crate::common::{
     destroy_i2c, new_i2c, BitFlags as BF, Register, ACCEL_ADDR, DEFAULT_CTRL_REG1_A,
 };
 use embedded_hal_mock::{delay::MockNoop as Delay, i2c::Transaction as I2cTrans};
-use lsm303agr::{AccelMode as Mode, AccelOutputDataRate as ODR, FifoMode, Interrupt};
+use lsm303agr::{
+    AccelMode as Mode, AccelOutputDataRate as ODR, FifoMode, HighPassFilterCutoff,
+    HighPassFilterMode, HighPassFilterTarget, Interrupt, InterruptPin, InterruptPolarity,
+};
 
 macro_rules! low_pwr {
     ($name:ident, $hz:ident, $value:expr) => {
@@ -207,6 +210,45 @@ fn can_enable_disable_interrupts() {
     destroy_i2c(sensor);
 }
 
+#[test]
+fn can_enable_disable_interrupts_on_pins() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG3_A, 0b01000000]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG6_A, 0b10000000]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG6_A, 0b10100000]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG6_A, 0b00100000]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG6_A, 0b00100010]),
+    ]);
+    sensor
+        .acc_enable_interrupt_on(InterruptPin::Int1, Interrupt::Aoi1)
+        .unwrap();
+    sensor
+        .acc_enable_interrupt_on(InterruptPin::Int2, Interrupt::Click)
+        .unwrap();
+    sensor
+        .acc_enable_interrupt_on(InterruptPin::Int2, Interrupt::Aoi2)
+        .unwrap();
+    sensor
+        .acc_disable_interrupt_on(InterruptPin::Int2, Interrupt::Click)
+        .unwrap();
+    sensor
+        .acc_set_interrupt_polarity(InterruptPolarity::ActiveLow)
+        .unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn cannot_route_data_ready_to_int2() {
+    let mut sensor = new_i2c(&[]);
+    sensor
+        .acc_enable_interrupt_on(InterruptPin::Int2, Interrupt::DataReady1)
+        .expect_err("should have returned error");
+    sensor
+        .acc_disable_interrupt_on(InterruptPin::Int2, Interrupt::FifoOverrun)
+        .expect_err("should have returned error");
+    destroy_i2c(sensor);
+}
+
 #[test]
 fn can_set_fifo_mode() {
     let mut sensor = new_i2c(&[
@@ -228,3 +270,28 @@ fn can_set_fifo_mode() {
     sensor.acc_set_fifo_mode(FifoMode::Bypass, 0).unwrap();
     destroy_i2c(sensor);
 }
+
+#[test]
+fn can_configure_high_pass_filter() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG2_A, 0b10010000]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG2_A, 0b10011000]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG2_A, 0b10011001]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG2_A, 0b10010001]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::REFERENCE_A], vec![0]),
+    ]);
+    sensor
+        .acc_set_high_pass_filter(HighPassFilterMode::Normal, HighPassFilterCutoff::High)
+        .unwrap();
+    sensor
+        .acc_enable_high_pass_filter(HighPassFilterTarget::OutputData)
+        .unwrap();
+    sensor
+        .acc_enable_high_pass_filter(HighPassFilterTarget::Aoi1)
+        .unwrap();
+    sensor
+        .acc_disable_high_pass_filter(HighPassFilterTarget::OutputData)
+        .unwrap();
+    sensor.acc_reset_hp_filter_reference().unwrap();
+    destroy_i2c(sensor);
+}
@@ -0,0 +1,342 @@
+#![cfg(feature = "async")]
+
+use embassy_futures::block_on;
+use embedded_hal_mock_1::eh1::{
+    delay::NoopDelay as Delay,
+    digital::{Mock as PinMock, State, Transaction as PinTrans},
+    i2c::{Mock as I2cMock, Transaction as I2cTrans},
+    spi::{Mock as SpiMock, Transaction as SpiTrans},
+    MockError,
+};
+use lsm303agr::{
+    interface, mode, AccelMode, AccelOutputDataRate, Error, Interrupt, InterruptPin,
+    InterruptPolarity, Lsm303agrAsync, MagMode, MagOutputDataRate,
+};
+mod common;
+use crate::common::{
+    BitFlags as BF, Register, ACCEL_ADDR, DEFAULT_CFG_REG_A_M, DEFAULT_CTRL_REG1_A, HZ50, MAG_ADDR,
+};
+
+fn new_i2c(
+    transactions: &[I2cTrans],
+) -> Lsm303agrAsync<interface::Eh1I2cInterface<I2cMock>, mode::MagOneShot> {
+    Lsm303agrAsync::new_with_i2c(I2cMock::new(transactions))
+}
+
+fn destroy_i2c<MODE>(sensor: Lsm303agrAsync<interface::Eh1I2cInterface<I2cMock>, MODE>) {
+    sensor.destroy().done();
+}
+
+fn new_spi(
+    accel_transactions: &[SpiTrans<u8>],
+    mag_transactions: &[SpiTrans<u8>],
+) -> Lsm303agrAsync<interface::Eh1SpiInterface<SpiMock<u8>, SpiMock<u8>>, mode::MagOneShot> {
+    Lsm303agrAsync::new_with_spi(
+        SpiMock::new(accel_transactions),
+        SpiMock::new(mag_transactions),
+    )
+}
+
+fn destroy_spi<MODE>(
+    sensor: Lsm303agrAsync<interface::Eh1SpiInterface<SpiMock<u8>, SpiMock<u8>>, MODE>,
+) {
+    let (mut spi_accel, mut spi_mag) = sensor.destroy();
+    spi_accel.done();
+    spi_mag.done();
+}
+
+fn spi_read(address: u8, response: Vec<u8>) -> Vec<SpiTrans<u8>> {
+    vec![
+        SpiTrans::transaction_start(),
+        SpiTrans::write(BF::SPI_RW | address),
+        SpiTrans::read_vec(response),
+        SpiTrans::transaction_end(),
+    ]
+}
+
+#[test]
+fn can_init_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, BF::ACCEL_BDU]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::TEMP_CFG_REG_A, BF::TEMP_EN0 | BF::TEMP_EN1],
+        ),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_C_M, BF::MAG_BDU]),
+        I2cTrans::write(
+            MAG_ADDR,
+            vec![
+                Register::CFG_REG_A_M,
+                DEFAULT_CFG_REG_A_M | BF::COMP_TEMP_EN,
+            ],
+        ),
+    ]);
+    block_on(sensor.init()).unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_get_ids_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::WHO_AM_I_A], vec![0x33]),
+        I2cTrans::write_read(MAG_ADDR, vec![Register::WHO_AM_I_M], vec![0x40]),
+    ]);
+    assert!(block_on(sensor.accelerometer_id()).unwrap().is_correct());
+    assert!(block_on(sensor.magnetometer_id()).unwrap().is_correct());
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_set_accel_mode_and_odr_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, BF::HR]),
+    ]);
+    block_on(sensor.set_accel_mode_and_odr(
+        &mut Delay,
+        AccelMode::HighResolution,
+        AccelOutputDataRate::Hz50,
+    ))
+    .unwrap();
+    assert_eq!(sensor.get_accel_mode(), AccelMode::HighResolution);
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn incompatible_accel_mode_and_odr_is_rejected() {
+    let mut sensor = new_i2c(&[]);
+    match block_on(sensor.set_accel_mode_and_odr(
+        &mut Delay,
+        AccelMode::Normal,
+        AccelOutputDataRate::Khz1_620LowPower,
+    )) {
+        Err(Error::InvalidInputData) => (),
+        _ => panic!("InvalidInputData not returned."),
+    }
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_set_mag_mode_and_odr_i2c() {
+    let mut sensor = new_i2c(&[I2cTrans::write(
+        MAG_ADDR,
+        vec![Register::CFG_REG_A_M, DEFAULT_CFG_REG_A_M | 2 << 2],
+    )]);
+    block_on(sensor.set_mag_mode_and_odr(
+        &mut Delay,
+        MagMode::HighResolution,
+        MagOutputDataRate::Hz50,
+    ))
+    .unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_take_one_shot_measurement_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 1]), // start measurement
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0]),
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0xFF]),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::OUTX_L_REG_M | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+    ]);
+    let data = block_on(sensor.magnetic_field(&mut Delay)).unwrap();
+    assert_eq!(data.xyz_raw(), (0x2010, 0x4030, 0x6050));
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn one_shot_measurement_discards_previous_data_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![BF::XYZDR]),
+        // discard previous measurement
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::OUTX_L_REG_M | 0x80],
+            vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        ),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 1]), // start measurement
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![BF::XYZDR]),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::OUTX_L_REG_M | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+    ]);
+    let data = block_on(sensor.magnetic_field(&mut Delay)).unwrap();
+    assert_eq!(data.xyz_raw(), (0x2010, 0x4030, 0x6050));
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn one_shot_measurement_times_out() {
+    let mut transactions = vec![
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 1]), // start measurement
+    ];
+    transactions.extend(
+        (0..10).map(|_| I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0])),
+    );
+    let mut sensor = new_i2c(&transactions);
+    match block_on(sensor.magnetic_field(&mut Delay)) {
+        Err(Error::Timeout) => (),
+        _ => panic!("Timeout not returned."),
+    }
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_change_mag_mode_i2c() {
+    let sensor = new_i2c(&[
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 0]),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::OUTX_L_REG_M | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 3]),
+    ]);
+    let mut sensor = block_on(sensor.into_mag_continuous()).ok().unwrap();
+    let data = block_on(sensor.magnetic_field()).unwrap();
+    assert_eq!(data.xyz_raw(), (0x2010, 0x4030, 0x6050));
+    let sensor = block_on(sensor.into_mag_one_shot()).ok().unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_read_acceleration_spi() {
+    let mut sensor = new_spi(
+        &spi_read(
+            BF::SPI_MS | Register::OUT_X_L_A,
+            vec![0x10, 0x00, 0x20, 0x00, 0x30, 0x00],
+        ),
+        &[],
+    );
+    let data = block_on(sensor.acceleration()).unwrap();
+    assert_eq!(data.xyz_raw(), (0x10, 0x20, 0x30));
+    destroy_spi(sensor);
+}
+
+#[test]
+fn can_get_status_spi() {
+    let mut sensor = new_spi(
+        &spi_read(Register::STATUS_REG_A, vec![BF::XYZDR]),
+        &spi_read(Register::STATUS_REG_M, vec![0]),
+    );
+    assert!(block_on(sensor.accel_status()).unwrap().xyz_new_data());
+    assert!(!block_on(sensor.mag_status()).unwrap().xyz_new_data());
+    destroy_spi(sensor);
+}
+
+fn wait_for(state: State) -> PinMock {
+    PinMock::new(&[PinTrans::wait_for_state(state)])
+}
+
+#[test]
+fn can_wait_for_accel_data() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG3_A, 0b00010000]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::STATUS_REG_A], vec![BF::XYZDR]),
+    ])
+    .with_int1_pin(wait_for(State::High));
+    block_on(sensor.acc_enable_interrupt(Interrupt::DataReady1)).unwrap();
+    assert!(block_on(sensor.wait_for_accel_data())
+        .unwrap()
+        .xyz_new_data());
+    let (sensor, mut int1, (), ()) = sensor.release_pins();
+    int1.done();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_wait_for_fifo_watermark() {
+    let mut sensor = new_i2c(&[I2cTrans::write_read(
+        ACCEL_ADDR,
+        vec![Register::FIFO_SRC_REG_A],
+        vec![0b10010011],
+    )])
+    .with_int1_pin(wait_for(State::High));
+    let status = block_on(sensor.wait_for_fifo_watermark()).unwrap();
+    assert!(status.watermark());
+    assert_eq!(status.len(), 19);
+    let (sensor, mut int1, (), ()) = sensor.release_pins();
+    int1.done();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_wait_for_click_with_active_low_pins() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG6_A, 0b10]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CLICK_SRC_A], vec![0b01010001]),
+    ])
+    .with_int1_pin(wait_for(State::Low));
+    block_on(sensor.acc_set_interrupt_polarity(InterruptPolarity::ActiveLow)).unwrap();
+    let source = block_on(sensor.wait_for_click()).unwrap();
+    assert!(source.single_click());
+    assert!(source.x());
+    let (sensor, mut int1, (), ()) = sensor.release_pins();
+    int1.done();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_wait_for_click_on_int2() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG6_A, 0b10000000]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CLICK_SRC_A], vec![0b01101100]),
+    ])
+    .with_int2_pin(wait_for(State::High));
+    block_on(sensor.acc_enable_interrupt_on(InterruptPin::Int2, Interrupt::Click)).unwrap();
+    let source = block_on(sensor.wait_for_click_int2()).unwrap();
+    assert!(source.double_click());
+    assert!(source.z());
+    let (sensor, (), mut int2, ()) = sensor.release_pins();
+    int2.done();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_wait_for_mag_data() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_C_M, BF::INT_MAG]),
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![BF::XYZDR]),
+    ])
+    .with_int_mag_pin(wait_for(State::High));
+    block_on(sensor.mag_enable_int()).unwrap();
+    assert!(block_on(sensor.wait_for_mag_data()).unwrap().xyz_new_data());
+    let (sensor, (), (), mut int_mag) = sensor.release_pins();
+    int_mag.done();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn cannot_route_data_ready_to_int2() {
+    let mut sensor = new_i2c(&[]);
+    assert!(matches!(
+        block_on(sensor.acc_enable_interrupt_on(InterruptPin::Int2, Interrupt::DataReady1)),
+        Err(Error::InvalidInputData)
+    ));
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn pin_error_is_returned() {
+    let mut sensor = new_i2c(&[])
+        .with_int1_pin(PinMock::new(&[PinTrans::wait_for_state(State::High)
+            .with_error(MockError::Io(std::io::ErrorKind::Other))]));
+    match block_on(sensor.wait_for_accel_data()) {
+        Err(Error::Pin(_)) => (),
+        _ => panic!("Pin error not returned."),
+    }
+    let (sensor, mut int1, (), ()) = sensor.release_pins();
+    int1.done();
+    destroy_i2c(sensor);
+}
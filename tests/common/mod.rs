@@ -24,16 +24,41 @@ impl Register {
     pub const WHO_AM_I_A: u8 = 0x0F;
     pub const TEMP_CFG_REG_A: u8 = 0x1F;
     pub const CTRL_REG1_A: u8 = 0x20;
+    pub const CTRL_REG2_A: u8 = 0x21;
     pub const CTRL_REG3_A: u8 = 0x22;
     pub const CTRL_REG4_A: u8 = 0x23;
     pub const CTRL_REG5_A: u8 = 0x24;
+    pub const CTRL_REG6_A: u8 = 0x25;
     pub const FIFO_CTRL_REG_A: u8 = 0x2E;
+    pub const FIFO_SRC_REG_A: u8 = 0x2F;
+    pub const REFERENCE_A: u8 = 0x26;
     pub const STATUS_REG_A: u8 = 0x27;
     pub const OUT_X_L_A: u8 = 0x28;
+    pub const INT1_CFG_A: u8 = 0x30;
+    pub const INT1_SRC_A: u8 = 0x31;
+    pub const INT1_THS_A: u8 = 0x32;
+    pub const INT1_DURATION_A: u8 = 0x33;
+    pub const INT2_CFG_A: u8 = 0x34;
+    pub const INT2_SRC_A: u8 = 0x35;
+    pub const INT2_THS_A: u8 = 0x36;
+    pub const INT2_DURATION_A: u8 = 0x37;
+    pub const CLICK_CFG_A: u8 = 0x38;
+    pub const CLICK_SRC_A: u8 = 0x39;
+    pub const CLICK_THS_A: u8 = 0x3A;
+    pub const TIME_LIMIT_A: u8 = 0x3B;
+    pub const TIME_LATENCY_A: u8 = 0x3C;
+    pub const TIME_WINDOW_A: u8 = 0x3D;
+    pub const ACT_THS_A: u8 = 0x3E;
+    pub const ACT_DUR_A: u8 = 0x3F;
+    pub const OFFSET_X_REG_L_M: u8 = 0x45;
     pub const WHO_AM_I_M: u8 = 0x4F;
     pub const CFG_REG_A_M: u8 = 0x60;
     pub const CFG_REG_B_M: u8 = 0x61;
     pub const CFG_REG_C_M: u8 = 0x62;
+    pub const INT_CTRL_REG_M: u8 = 0x63;
+    pub const INT_SOURCE_REG_M: u8 = 0x64;
+    pub const INT_THS_L_REG_M: u8 = 0x65;
+    pub const INT_THS_H_REG_M: u8 = 0x66;
     pub const STATUS_REG_M: u8 = 0x67;
     pub const OUTX_L_REG_M: u8 = 0x68;
 }
@@ -50,9 +75,15 @@ impl BitFlags {
     pub const LP_EN: u8 = 1 << 3;
 
     pub const ACCEL_BDU: u8 = 1 << 7;
+    pub const SIM: u8 = 1;
     pub const HR: u8 = 1 << 3;
+    pub const ACCEL_BLE: u8 = 1 << 6;
 
     pub const MAG_BDU: u8 = 1 << 4;
+    pub const COMP_TEMP_EN: u8 = 1 << 7;
+    pub const MAG_BLE: u8 = 1 << 3;
+    pub const I2C_DIS: u8 = 1 << 5;
+    pub const INT_MAG: u8 = 1;
 
     pub const MAG_OFF_CANC: u8 = 1 << 1;
     pub const MAG_OFF_CANC_ONE_SHOT: u8 = 1 << 4;
@@ -84,8 +115,8 @@ pub fn default_cs_n(n: usize) -> PinMock {
         &[PinTrans::set(PinState::Low), PinTrans::set(PinState::High)]
             .iter()
             .cycle()
-            .cloned()
             .take(n * 2)
+            .cloned()
             .collect::<Vec<_>>(),
     )
 }
@@ -115,6 +146,15 @@ pub fn new_spi(
     Lsm303agr::new_with_spi(SpiMock::new(transactions), accel_cs, mag_cs)
 }
 
+#[allow(unused)]
+pub fn new_spi_3_wire(
+    transactions: &[SpiTrans],
+    accel_cs: PinMock,
+    mag_cs: PinMock,
+) -> Lsm303agr<interface::SpiInterface<SpiMock, PinMock, PinMock>, mode::MagOneShot> {
+    Lsm303agr::new_with_spi_3_wire(SpiMock::new(transactions), accel_cs, mag_cs)
+}
+
 #[allow(unused)]
 pub fn destroy_spi<MODE>(
     sensor: Lsm303agr<interface::SpiInterface<SpiMock, PinMock, PinMock>, MODE>,
@@ -0,0 +1,259 @@
+#![cfg(feature = "eh1")]
+
+use embedded_hal_mock_1::eh1::{
+    delay::NoopDelay,
+    i2c::{Mock as I2cMock, Transaction as I2cTrans},
+    spi::{Mock as SpiMock, Transaction as SpiTrans},
+};
+use lsm303agr::{
+    interface, mode, AccelMode, AccelOutputDataRate, AccelScale, Eh1Delay, Error, HardIronOffset,
+    Lsm303agr,
+};
+mod common;
+use crate::common::{
+    BitFlags as BF, Register, ACCEL_ADDR, DEFAULT_CFG_REG_A_M, DEFAULT_CTRL_REG1_A, MAG_ADDR,
+};
+
+fn new_i2c(
+    transactions: &[I2cTrans],
+) -> Lsm303agr<interface::Eh1I2cInterface<I2cMock>, mode::MagOneShot> {
+    Lsm303agr::new_with_eh1_i2c(I2cMock::new(transactions))
+}
+
+fn destroy_i2c<MODE>(sensor: Lsm303agr<interface::Eh1I2cInterface<I2cMock>, MODE>) {
+    sensor.destroy().done();
+}
+
+fn new_spi(
+    accel_transactions: &[SpiTrans<u8>],
+    mag_transactions: &[SpiTrans<u8>],
+) -> Lsm303agr<interface::Eh1SpiInterface<SpiMock<u8>, SpiMock<u8>>, mode::MagOneShot> {
+    Lsm303agr::new_with_eh1_spi(
+        SpiMock::new(accel_transactions),
+        SpiMock::new(mag_transactions),
+    )
+}
+
+fn destroy_spi<MODE>(
+    sensor: Lsm303agr<interface::Eh1SpiInterface<SpiMock<u8>, SpiMock<u8>>, MODE>,
+) {
+    let (mut spi_accel, mut spi_mag) = sensor.destroy();
+    spi_accel.done();
+    spi_mag.done();
+}
+
+fn spi_write(payload: Vec<u8>) -> Vec<SpiTrans<u8>> {
+    vec![
+        SpiTrans::transaction_start(),
+        SpiTrans::write_vec(payload),
+        SpiTrans::transaction_end(),
+    ]
+}
+
+fn spi_read(address: u8, response: Vec<u8>) -> Vec<SpiTrans<u8>> {
+    vec![
+        SpiTrans::transaction_start(),
+        SpiTrans::write(BF::SPI_RW | address),
+        SpiTrans::read_vec(response),
+        SpiTrans::transaction_end(),
+    ]
+}
+
+#[test]
+fn can_get_ids_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::WHO_AM_I_A], vec![0x33]),
+        I2cTrans::write_read(MAG_ADDR, vec![Register::WHO_AM_I_M], vec![0x40]),
+    ]);
+    assert!(sensor.accelerometer_id().unwrap().is_correct());
+    assert!(sensor.magnetometer_id().unwrap().is_correct());
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_read_acceleration_i2c() {
+    let mut sensor = new_i2c(&[I2cTrans::write_read(
+        ACCEL_ADDR,
+        vec![Register::OUT_X_L_A | 0x80],
+        vec![0x10, 0x00, 0x20, 0x00, 0x30, 0x00],
+    )]);
+    let data = sensor.acceleration().unwrap();
+    assert_eq!(data.xyz_raw(), (0x10, 0x20, 0x30));
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_set_accel_mode_and_odr_with_eh1_delay_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, 5 << 4 | DEFAULT_CTRL_REG1_A],
+        ),
+    ]);
+    sensor
+        .set_accel_mode_and_odr(
+            &mut Eh1Delay(NoopDelay::new()),
+            AccelMode::Normal,
+            AccelOutputDataRate::Hz100,
+        )
+        .unwrap();
+    destroy_i2c(sensor);
+}
+
+/// Delay implementing both embedded-hal 0.2 and 1.0 traits, as many HALs do.
+struct BothDelay;
+
+impl embedded_hal::blocking::delay::DelayUs<u32> for BothDelay {
+    fn delay_us(&mut self, _us: u32) {}
+}
+
+impl embedded_hal_1::delay::DelayNs for BothDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+#[test]
+fn can_set_accel_mode_and_odr_with_delay_implementing_both_traits_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, 5 << 4 | DEFAULT_CTRL_REG1_A],
+        ),
+    ]);
+    sensor
+        .set_accel_mode_and_odr(
+            &mut BothDelay,
+            AccelMode::Normal,
+            AccelOutputDataRate::Hz100,
+        )
+        .unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_set_mag_hard_iron_offset_i2c() {
+    let mut sensor = new_i2c(&[I2cTrans::write(
+        MAG_ADDR,
+        vec![
+            Register::OFFSET_X_REG_L_M | 0x80,
+            0x01,
+            0x00,
+            0x02,
+            0x00,
+            0x03,
+            0x00,
+        ],
+    )]);
+    sensor
+        .set_mag_hard_iron_offset(HardIronOffset::from_unscaled(1, 2, 3))
+        .unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_verify_writes_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0x10]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CTRL_REG4_A], vec![0x00]),
+    ]);
+    sensor.set_verify_writes(true);
+    match sensor.set_accel_scale(AccelScale::G4) {
+        Err(Error::VerifyFailed {
+            register: Register::CTRL_REG4_A,
+            expected: 0x10,
+            actual: 0x00,
+        }) => (),
+        _ => panic!("Write verification did not fail."),
+    }
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_get_ids_spi() {
+    let mut sensor = new_spi(
+        &spi_read(Register::WHO_AM_I_A, vec![0x33]),
+        &spi_read(Register::WHO_AM_I_M, vec![0x40]),
+    );
+    assert!(sensor.accelerometer_id().unwrap().is_correct());
+    assert!(sensor.magnetometer_id().unwrap().is_correct());
+    destroy_spi(sensor);
+}
+
+#[test]
+fn can_read_acceleration_spi() {
+    let mut sensor = new_spi(
+        &spi_read(
+            BF::SPI_MS | Register::OUT_X_L_A,
+            vec![0x10, 0x00, 0x20, 0x00, 0x30, 0x00],
+        ),
+        &[],
+    );
+    let data = sensor.acceleration().unwrap();
+    assert_eq!(data.xyz_raw(), (0x10, 0x20, 0x30));
+    destroy_spi(sensor);
+}
+
+#[test]
+fn can_read_mag_status_spi() {
+    let mut sensor = new_spi(&[], &spi_read(Register::STATUS_REG_M, vec![BF::XYZDR]));
+    assert!(sensor.mag_status().unwrap().xyz_new_data());
+    destroy_spi(sensor);
+}
+
+#[test]
+fn can_set_mag_hard_iron_offset_spi() {
+    let mut sensor = new_spi(
+        &[],
+        &spi_write(vec![
+            BF::SPI_MS | Register::OFFSET_X_REG_L_M,
+            0x01,
+            0x00,
+            0x02,
+            0x00,
+            0x03,
+            0x00,
+        ]),
+    );
+    sensor
+        .set_mag_hard_iron_offset(HardIronOffset::from_unscaled(1, 2, 3))
+        .unwrap();
+    destroy_spi(sensor);
+}
+
+#[test]
+fn can_verify_writes_spi() {
+    let mut txns = spi_write(vec![Register::CTRL_REG4_A, 0x10]);
+    txns.extend(spi_read(Register::CTRL_REG4_A, vec![0x10]));
+    let mut sensor = new_spi(&txns, &[]);
+    sensor.set_verify_writes(true);
+    sensor.set_accel_scale(AccelScale::G4).unwrap();
+    destroy_spi(sensor);
+}
+
+#[test]
+fn can_init_3_wire_spi() {
+    let accel = [
+        spi_write(vec![Register::CTRL_REG4_A, BF::ACCEL_BDU | BF::SIM]),
+        spi_write(vec![Register::TEMP_CFG_REG_A, BF::TEMP_EN0 | BF::TEMP_EN1]),
+    ]
+    .concat();
+    let mag = [
+        spi_write(vec![Register::CFG_REG_C_M, BF::MAG_BDU | BF::I2C_DIS]),
+        spi_write(vec![
+            Register::CFG_REG_A_M,
+            DEFAULT_CFG_REG_A_M | BF::COMP_TEMP_EN,
+        ]),
+    ]
+    .concat();
+    let mut sensor = Lsm303agr::new_with_eh1_spi_3_wire(SpiMock::new(&accel), SpiMock::new(&mag));
+    sensor.init().unwrap();
+    destroy_spi(sensor);
+}
+
+#[test]
+fn can_disable_mag_i2c_spi() {
+    let mut sensor = new_spi(&[], &spi_write(vec![Register::CFG_REG_C_M, BF::I2C_DIS]));
+    sensor.mag_disable_i2c().unwrap();
+    destroy_spi(sensor);
+}
@@ -0,0 +1,88 @@
+mod common;
+use crate::common::{
+    default_cs_n, destroy_i2c, destroy_spi, new_i2c, new_spi_accel, BitFlags as BF, Register,
+    ACCEL_ADDR,
+};
+use embedded_hal_mock::{i2c::Transaction as I2cTrans, spi::Transaction as SpiTrans};
+use lsm303agr::Acceleration;
+
+#[test]
+fn can_get_fifo_status() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::FIFO_SRC_REG_A], vec![0b00100000]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::FIFO_SRC_REG_A], vec![0b10010011]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::FIFO_SRC_REG_A], vec![0b11011111]),
+    ]);
+
+    let status = sensor.acc_fifo_status().unwrap();
+    assert!(status.is_empty());
+    assert!(!status.watermark());
+    assert!(!status.overrun());
+    assert_eq!(status.len(), 0);
+
+    let status = sensor.acc_fifo_status().unwrap();
+    assert!(!status.is_empty());
+    assert!(status.watermark());
+    assert!(!status.overrun());
+    assert_eq!(status.len(), 19);
+
+    let status = sensor.acc_fifo_status().unwrap();
+    assert!(status.watermark());
+    assert!(status.overrun());
+    assert_eq!(status.len(), 32);
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_read_fifo_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::FIFO_SRC_REG_A], vec![2]),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![
+                0x10, 0x20, 0x30, 0x40, 0x50, 0x60, //
+                0x11, 0x21, 0x31, 0x41, 0x51, 0x61,
+            ],
+        ),
+    ]);
+
+    let mut data = [Acceleration::default(); 4];
+    assert_eq!(sensor.acc_read_fifo(&mut data).unwrap(), 2);
+    assert_eq!(data[0].xyz_raw(), (0x2010, 0x4030, 0x6050));
+    assert_eq!(data[1].xyz_raw(), (0x2111, 0x4131, 0x6151));
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_read_fifo_up_to_buffer_length_spi() {
+    let mut sensor = new_spi_accel(
+        &[
+            SpiTrans::transfer(
+                vec![Register::FIFO_SRC_REG_A | BF::SPI_RW, 0],
+                vec![0, 0b01000000],
+            ),
+            SpiTrans::transfer(
+                vec![
+                    Register::OUT_X_L_A | BF::SPI_RW | BF::SPI_MS,
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                ],
+                vec![0, 0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+            ),
+        ],
+        default_cs_n(2),
+    );
+
+    let mut data = [Acceleration::default(); 1];
+    assert_eq!(sensor.acc_read_fifo(&mut data).unwrap(), 1);
+    assert_eq!(data[0].xyz_raw(), (0x2010, 0x4030, 0x6050));
+
+    destroy_spi(sensor);
+}
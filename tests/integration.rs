@@ -1,7 +1,7 @@
 mod common;
 use crate::common::{
     default_cs, default_cs_n, destroy_i2c, destroy_spi, new_i2c, new_spi, new_spi_accel,
-    new_spi_mag, BitFlags as BF, Register, ACCEL_ADDR, MAG_ADDR,
+    new_spi_mag, BitFlags as BF, Register, ACCEL_ADDR, DEFAULT_CFG_REG_A_M, MAG_ADDR,
 };
 use embedded_hal_mock::{
     i2c::Transaction as I2cTrans, pin::Mock as PinMock, spi::Transaction as SpiTrans,
@@ -164,6 +164,13 @@ fn can_init_i2c() {
             vec![Register::TEMP_CFG_REG_A, BF::TEMP_EN1 | BF::TEMP_EN0],
         ),
         I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_C_M, BF::MAG_BDU]),
+        I2cTrans::write(
+            MAG_ADDR,
+            vec![
+                Register::CFG_REG_A_M,
+                DEFAULT_CFG_REG_A_M | BF::COMP_TEMP_EN,
+            ],
+        ),
     ]);
     sensor.init().unwrap();
     destroy_i2c(sensor);
@@ -175,11 +182,38 @@ fn can_init_spi() {
         &[
             SpiTrans::write(vec![Register::CTRL_REG4_A, BF::ACCEL_BDU]),
             SpiTrans::write(vec![Register::TEMP_CFG_REG_A, BF::TEMP_EN1 | BF::TEMP_EN0]),
-            SpiTrans::write(vec![Register::CFG_REG_C_M, BF::MAG_BDU]),
+            SpiTrans::write(vec![Register::CFG_REG_C_M, BF::MAG_BDU | BF::I2C_DIS]),
+            SpiTrans::write(vec![
+                Register::CFG_REG_A_M,
+                DEFAULT_CFG_REG_A_M | BF::COMP_TEMP_EN,
+            ]),
         ],
         default_cs_n(2),
-        default_cs(),
+        default_cs_n(2),
     );
     sensor.init().unwrap();
     destroy_spi(sensor);
 }
+
+#[test]
+fn can_disable_mag_i2c() {
+    let mut sensor = new_spi_mag(
+        &[SpiTrans::write(vec![Register::CFG_REG_C_M, BF::I2C_DIS])],
+        default_cs(),
+    );
+    sensor.mag_disable_i2c().unwrap();
+    destroy_spi(sensor);
+}
+
+#[test]
+fn mag_i2c_stays_disabled_on_spi() {
+    let mut sensor = new_spi_mag(
+        &[SpiTrans::write(vec![
+            Register::CFG_REG_C_M,
+            BF::INT_MAG | BF::I2C_DIS,
+        ])],
+        default_cs(),
+    );
+    sensor.mag_enable_int().unwrap();
+    destroy_spi(sensor);
+}
@@ -1,6 +1,7 @@
 mod common;
-use crate::common::{destroy_i2c, new_i2c, Register, MAG_ADDR};
-use embedded_hal_mock::i2c::Transaction as I2cTrans;
+use crate::common::{destroy_i2c, new_i2c, Register, ACCEL_ADDR, DEFAULT_CTRL_REG1_A, MAG_ADDR};
+use embedded_hal_mock::{delay::MockNoop as Delay, i2c::Transaction as I2cTrans};
+use lsm303agr::{AccelMode, AccelOutputDataRate as ODR, InterruptConfig};
 
 #[test]
 fn can_change_into_continuous() {
@@ -19,3 +20,27 @@ fn can_change_into_one_shot() {
     let sensor = sensor.into_mag_one_shot().ok().unwrap();
     destroy_i2c(sensor);
 }
+
+#[test]
+fn keeps_accel_odr_on_mode_change() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, 5 << 4 | DEFAULT_CTRL_REG1_A],
+        ),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 0]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::INT1_THS_A, 0]),
+        // 100 ms * 100 Hz
+        I2cTrans::write(ACCEL_ADDR, vec![Register::INT1_DURATION_A, 10]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::INT1_CFG_A, 0]),
+    ]);
+    sensor
+        .set_accel_mode_and_odr(&mut Delay, AccelMode::Normal, ODR::Hz100)
+        .unwrap();
+    let mut sensor = sensor.into_mag_continuous().ok().unwrap();
+    sensor
+        .acc_configure_int1(InterruptConfig::default().duration_ms(100))
+        .unwrap();
+    destroy_i2c(sensor);
+}
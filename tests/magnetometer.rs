@@ -10,7 +10,10 @@ use embedded_hal_mock::{
     pin::{Mock as PinMock, State as PinState, Transaction as PinTrans},
     spi::Transaction as SpiTrans,
 };
-use lsm303agr::{MagMode, MagOutputDataRate as ODR};
+use lsm303agr::{
+    Endianness, Error, HardIronOffset, InterruptPolarity, MagInterruptConfig,
+    MagInterruptDataSource, MagMode, MagOutputDataRate as ODR, MagSetPulseFrequency,
+};
 
 macro_rules! set_mag_odr {
     ($name:ident, $hz:ident, $value:expr) => {
@@ -33,6 +36,7 @@ set_mag_odr!(set_mag_odr_hz50, Hz50, 2 << 2);
 set_mag_odr!(set_mag_odr_hz100, Hz100, 3 << 2);
 
 #[test]
+#[allow(clippy::identity_op)]
 fn can_change_mode() {
     let mut sensor = new_i2c(&[
         // Set low-power mode
@@ -101,6 +105,50 @@ fn can_take_one_shot_measurement_i2c() {
     destroy_i2c(sensor);
 }
 
+#[test]
+fn can_take_blocking_one_shot_measurement_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![BF::XYZDR]),
+        // discard previous measurement
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::OUTX_L_REG_M | 0x80],
+            vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        ),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 1]), // start measurement
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0]),
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![BF::XYZDR]),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::OUTX_L_REG_M | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+    ]);
+    let data = sensor.magnetic_field_blocking(&mut Delay).unwrap();
+
+    assert_eq!(data.xyz_raw(), (0x2010, 0x4030, 0x6050));
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn blocking_one_shot_measurement_times_out() {
+    let mut transactions = vec![
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 1]), // start measurement
+    ];
+    transactions.extend(
+        (0..10).map(|_| I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0])),
+    );
+    let mut sensor = new_i2c(&transactions);
+    match sensor.magnetic_field_blocking(&mut Delay) {
+        Err(Error::Timeout) => (),
+        _ => panic!("Timeout not returned."),
+    }
+
+    destroy_i2c(sensor);
+}
+
 #[test]
 fn can_take_continuous_measurement_i2c() {
     let sensor = new_i2c(&[
@@ -131,6 +179,28 @@ fn can_take_continuous_measurement_i2c() {
     destroy_i2c(sensor);
 }
 
+#[test]
+fn can_take_big_endian_measurement_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_C_M, BF::MAG_BLE]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 0]),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::OUTX_L_REG_M | 0x80],
+            vec![0x20, 0x10, 0x40, 0x30, 0x60, 0x50],
+        ),
+    ]);
+    sensor.mag_set_endianness(Endianness::Big).unwrap();
+    let mut sensor = sensor.into_mag_continuous().ok().unwrap();
+    let data = sensor.magnetic_field().unwrap();
+
+    assert_eq!(data.x_raw(), 0x2010);
+    assert_eq!(data.y_raw(), 0x4030);
+    assert_eq!(data.z_raw(), 0x6050);
+
+    destroy_i2c(sensor);
+}
+
 #[test]
 fn can_take_continuous_measurement_spi() {
     let sensor = new_spi_mag(
@@ -277,3 +347,189 @@ fn can_disable_mag_low_pass_filter() {
 
     destroy_i2c(sensor);
 }
+
+#[test]
+fn mag_temp_compensation_is_kept_on_mode_and_odr_change() {
+    let mut sensor = new_i2c(&[
+        // Enable temperature compensation
+        I2cTrans::write(
+            MAG_ADDR,
+            vec![
+                Register::CFG_REG_A_M,
+                DEFAULT_CFG_REG_A_M | BF::COMP_TEMP_EN,
+            ],
+        ),
+        // Set low-power mode at 50 Hz
+        I2cTrans::write(
+            MAG_ADDR,
+            vec![
+                Register::CFG_REG_A_M,
+                DEFAULT_CFG_REG_A_M | BF::COMP_TEMP_EN | 0b00011000,
+            ],
+        ),
+        // Change into continuous mode
+        I2cTrans::write(
+            MAG_ADDR,
+            vec![Register::CFG_REG_A_M, BF::COMP_TEMP_EN | 0b00011000],
+        ),
+        // Disable temperature compensation
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 0b00011000]),
+    ]);
+
+    sensor
+        .mag_enable_temp_compensation()
+        .expect("failed to enable temperature compensation");
+    sensor
+        .set_mag_mode_and_odr(&mut Delay, MagMode::LowPower, ODR::Hz50)
+        .unwrap();
+    let mut sensor = sensor.into_mag_continuous().ok().unwrap();
+    sensor
+        .mag_disable_temp_compensation()
+        .expect("failed to disable temperature compensation");
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_configure_mag_int() {
+    let mut sensor = new_i2c(&[
+        // 300000 nT / 150 nT = 2000 = 0x07D0
+        I2cTrans::write(MAG_ADDR, vec![Register::INT_THS_L_REG_M, 0xD0]),
+        I2cTrans::write(MAG_ADDR, vec![Register::INT_THS_H_REG_M, 0x07]),
+        I2cTrans::write(MAG_ADDR, vec![Register::INT_CTRL_REG_M, 0b10100111]),
+        // Clamped, active-low, pulsed, disabled
+        I2cTrans::write(MAG_ADDR, vec![Register::INT_THS_L_REG_M, 0xFF]),
+        I2cTrans::write(MAG_ADDR, vec![Register::INT_THS_H_REG_M, 0xFF]),
+        I2cTrans::write(MAG_ADDR, vec![Register::INT_CTRL_REG_M, 0]),
+    ]);
+
+    sensor
+        .mag_configure_int(
+            MagInterruptConfig::default()
+                .x(true)
+                .z(true)
+                .polarity(InterruptPolarity::ActiveHigh)
+                .latched(true)
+                .threshold_nt(300_000),
+        )
+        .unwrap();
+    sensor
+        .mag_configure_int(
+            MagInterruptConfig::default()
+                .polarity(InterruptPolarity::ActiveLow)
+                .threshold_nt(u32::MAX),
+        )
+        .unwrap();
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_set_mag_int_data_source() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_B_M, 0b1000]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_B_M, 0b0000]),
+    ]);
+    sensor
+        .mag_set_int_data_source(MagInterruptDataSource::HardIronCorrected)
+        .unwrap();
+    sensor
+        .mag_set_int_data_source(MagInterruptDataSource::Uncorrected)
+        .unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_set_mag_set_pulse_frequency() {
+    let mut sensor = new_i2c(&[
+        // Enable low-pass filter
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_B_M, 0b0001]),
+        // Set pulse only at power-on, low-pass filter stays enabled
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_B_M, 0b0101]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_B_M, 0b0001]),
+    ]);
+    sensor.mag_enable_low_pass_filter().unwrap();
+    sensor
+        .mag_set_pulse_frequency(MagSetPulseFrequency::PowerOnOnly)
+        .unwrap();
+    sensor
+        .mag_set_pulse_frequency(MagSetPulseFrequency::Every63Odr)
+        .unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_get_mag_int_source() {
+    let mut sensor = new_i2c(&[I2cTrans::write_read(
+        MAG_ADDR,
+        vec![Register::INT_SOURCE_REG_M],
+        vec![0b10001011],
+    )]);
+
+    let source = sensor.mag_int_source().unwrap();
+    assert!(source.interrupt());
+    assert!(source.overflow());
+    assert!(source.x_positive());
+    assert!(source.y_negative());
+    assert!(!source.y_positive());
+    assert!(!source.z_positive());
+    assert!(!source.x_negative());
+    assert!(!source.z_negative());
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_set_and_get_mag_hard_iron_offset_i2c() {
+    let mut sensor = new_i2c(&[
+        // -300 nT / 150 nT = -2, 1500 nT / 150 nT = 10, clamped
+        I2cTrans::write(
+            MAG_ADDR,
+            vec![
+                Register::OFFSET_X_REG_L_M | 0x80,
+                0xFE,
+                0xFF,
+                0x0A,
+                0x00,
+                0xFF,
+                0x7F,
+            ],
+        ),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 0]),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::OFFSET_X_REG_L_M | 0x80],
+            vec![0xFE, 0xFF, 0x0A, 0x00, 0xFF, 0x7F],
+        ),
+    ]);
+    sensor
+        .set_mag_hard_iron_offset(HardIronOffset::from_nt(-300, 1500, i32::MAX))
+        .unwrap();
+    let mut sensor = sensor.into_mag_continuous().ok().unwrap();
+    let offset = sensor.mag_hard_iron_offset().unwrap();
+
+    assert_eq!(offset.xyz_unscaled(), (-2, 10, i16::MAX));
+    assert_eq!(offset.xyz_nt(), (-300, 1500, i16::MAX as i32 * 150));
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_set_mag_hard_iron_offset_spi() {
+    let mut sensor = new_spi_mag(
+        &[SpiTrans::write(vec![
+            Register::OFFSET_X_REG_L_M | BF::SPI_MS,
+            0x01,
+            0x00,
+            0x02,
+            0x00,
+            0x03,
+            0x00,
+        ])],
+        PinMock::new(&[PinTrans::set(PinState::Low), PinTrans::set(PinState::High)]),
+    );
+    sensor
+        .set_mag_hard_iron_offset(HardIronOffset::from_unscaled(1, 2, 3))
+        .unwrap();
+    destroy_spi(sensor);
+}
@@ -6,7 +6,7 @@ use crate::common::{
 use embedded_hal_mock::{
     delay::MockNoop as Delay, i2c::Transaction as I2cTrans, spi::Transaction as SpiTrans,
 };
-use lsm303agr::{AccelMode, AccelOutputDataRate, AccelScale};
+use lsm303agr::{AccelMode, AccelOutputDataRate, AccelScale, AxisSet, Endianness};
 
 fn i2c_mode_txns(mode: &AccelMode) -> Vec<I2cTrans> {
     match mode {
@@ -97,6 +97,7 @@ macro_rules! can_get_i2c {
 }
 
 #[rustfmt::skip]
+#[allow(clippy::identity_op)]
 mod can_get_i2c {
     use super::*;
 
@@ -328,3 +329,120 @@ fn can_get_12_bit_data_i2c() {
 
     destroy_i2c(sensor);
 }
+
+#[test]
+fn can_get_12_bit_big_endian_data_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, BF::HR]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG4_A, BF::HR | BF::ACCEL_BLE],
+        ),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x20, 0x10, 0x40, 0x30, 0x60, 0x50],
+        ),
+    ]);
+    sensor
+        .set_accel_mode_and_odr(
+            &mut Delay,
+            AccelMode::HighResolution,
+            AccelOutputDataRate::Hz50,
+        )
+        .unwrap();
+    sensor.acc_set_endianness(Endianness::Big).unwrap();
+    let data = sensor.acceleration().unwrap();
+
+    assert_eq!(data.x_raw(), 0x2010);
+    assert_eq!(data.y_raw(), 0x4030);
+    assert_eq!(data.z_raw(), 0x6050);
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn cannot_set_big_endian_outside_high_resolution_mode() {
+    let mut sensor = new_i2c(&[]);
+    sensor
+        .acc_set_endianness(Endianness::Big)
+        .expect_err("should have returned error");
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn leaving_high_resolution_mode_restores_little_endian() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, BF::HR]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG4_A, BF::HR | BF::ACCEL_BLE],
+        ),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+    ]);
+    sensor
+        .set_accel_mode_and_odr(
+            &mut Delay,
+            AccelMode::HighResolution,
+            AccelOutputDataRate::Hz50,
+        )
+        .unwrap();
+    sensor.acc_set_endianness(Endianness::Big).unwrap();
+    sensor
+        .set_accel_mode_and_odr(&mut Delay, AccelMode::Normal, AccelOutputDataRate::Hz50)
+        .unwrap();
+    let data = sensor.acceleration().unwrap();
+
+    assert_eq!(data.x_raw(), 0x2010);
+    assert_eq!(data.y_raw(), 0x4030);
+    assert_eq!(data.z_raw(), 0x6050);
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_get_data_of_enabled_axes_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG1_A, 0b100]),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x40],
+        ),
+    ]);
+    sensor.set_accel_axes(AxisSet::none().z(true)).unwrap();
+    assert_eq!(sensor.get_accel_axes(), AxisSet::none().z(true));
+    let data = sensor.acceleration().unwrap();
+
+    assert!(data.axes().contains_z());
+    assert_eq!(data.enabled_x_mg(), None);
+    assert_eq!(data.enabled_y_mg(), None);
+    assert_eq!(data.enabled_z_mg(), Some(data.z_mg()));
+    assert_eq!(data.enabled_xyz_mg(), (None, None, Some(data.z_mg())));
+    assert_eq!(data.enabled_x_raw(), None);
+    assert_eq!(data.enabled_xyz_raw(), (None, None, Some(0x4000)));
+    assert_eq!(data.enabled_y_unscaled(), None);
+    assert_eq!(
+        data.enabled_xyz_unscaled(),
+        (None, None, Some(data.z_unscaled()))
+    );
+
+    destroy_i2c(sensor);
+}
@@ -0,0 +1,69 @@
+mod common;
+use crate::common::{
+    destroy_i2c, new_i2c, Register, ACCEL_ADDR, DEFAULT_CFG_REG_A_M, DEFAULT_CTRL_REG1_A, MAG_ADDR,
+};
+use embedded_hal_mock::{delay::MockNoop as Delay, i2c::Transaction as I2cTrans};
+use lsm303agr::{AccelMode, AccelOutputDataRate as ODR};
+
+fn accel_reboot() -> Vec<I2cTrans> {
+    vec![
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG5_A, 0b10000000]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG2_A, 0]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG3_A, 0]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG5_A, 0]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG6_A, 0]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::TEMP_CFG_REG_A, 0]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::FIFO_CTRL_REG_A, 0]),
+    ]
+}
+
+#[test]
+fn can_reboot_accel() {
+    let mut transactions = vec![
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, 5 << 4 | DEFAULT_CTRL_REG1_A],
+        ),
+    ];
+    transactions.extend(accel_reboot());
+
+    let mut sensor = new_i2c(&transactions);
+    sensor
+        .set_accel_mode_and_odr(&mut Delay, AccelMode::Normal, ODR::Hz100)
+        .unwrap();
+    sensor.acc_reboot(&mut Delay).unwrap();
+    assert_eq!(sensor.get_accel_mode(), AccelMode::PowerDown);
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_soft_reset_mag_from_continuous() {
+    let sensor = new_i2c(&[
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 0]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_B_M, 0b10]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 0b00100000]),
+        // Back in idle mode after reset.
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 0]),
+    ]);
+    let mut sensor = sensor.into_mag_continuous().ok().unwrap();
+    sensor.enable_mag_offset_cancellation().unwrap();
+    let sensor = sensor.mag_soft_reset(&mut Delay).ok().unwrap();
+    let sensor = sensor.into_mag_continuous().ok().unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_reset() {
+    let mut transactions = accel_reboot();
+    transactions.push(I2cTrans::write(
+        MAG_ADDR,
+        vec![Register::CFG_REG_A_M, 0b00100000 | DEFAULT_CFG_REG_A_M],
+    ));
+
+    let sensor = new_i2c(&transactions);
+    let sensor = sensor.reset(&mut Delay).ok().unwrap();
+    destroy_i2c(sensor);
+}
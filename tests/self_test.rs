@@ -0,0 +1,202 @@
+mod common;
+use crate::common::{
+    destroy_i2c, new_i2c, Register, ACCEL_ADDR, DEFAULT_CFG_REG_A_M, DEFAULT_CTRL_REG1_A, MAG_ADDR,
+};
+use embedded_hal_mock::{delay::MockNoop as Delay, i2c::Transaction as I2cTrans};
+use lsm303agr::{Error, FifoMode, HighPassFilterTarget};
+
+fn accel_samples(x: i16, y: i16, z: i16) -> Vec<I2cTrans> {
+    // Normal mode output is left-justified 10-bit.
+    let data = [x, y, z]
+        .iter()
+        .flat_map(|v| ((v << 6) as u16).to_le_bytes())
+        .collect::<Vec<_>>();
+
+    // The first sample is discarded.
+    (0..6)
+        .flat_map(|_| {
+            vec![
+                I2cTrans::write_read(ACCEL_ADDR, vec![Register::STATUS_REG_A], vec![0b1000]),
+                I2cTrans::write_read(ACCEL_ADDR, vec![Register::OUT_X_L_A | 0x80], data.clone()),
+            ]
+        })
+        .collect()
+}
+
+fn mag_samples(x: i16, y: i16, z: i16) -> Vec<I2cTrans> {
+    let data = [x, y, z]
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect::<Vec<_>>();
+
+    // The first sample is discarded.
+    (0..51)
+        .flat_map(|_| {
+            vec![
+                I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0b1000]),
+                I2cTrans::write_read(MAG_ADDR, vec![Register::OUTX_L_REG_M | 0x80], data.clone()),
+            ]
+        })
+        .collect()
+}
+
+fn accel_restore_txns() -> Vec<I2cTrans> {
+    vec![
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG2_A, 0]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::FIFO_CTRL_REG_A, 0]),
+    ]
+}
+
+#[test]
+fn can_run_accel_self_test() {
+    let mut transactions = vec![
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG2_A, 0]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::FIFO_CTRL_REG_A, 0]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0b10000000]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, 5 << 4 | DEFAULT_CTRL_REG1_A],
+        ),
+        // No data available yet.
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::STATUS_REG_A], vec![0]),
+    ];
+    transactions.extend(accel_samples(-10, 0, 250));
+    transactions.push(I2cTrans::write(
+        ACCEL_ADDR,
+        vec![Register::CTRL_REG4_A, 0b10000010],
+    ));
+    transactions.extend(accel_samples(40, 100, 260));
+    transactions.extend(accel_restore_txns());
+
+    let mut sensor = new_i2c(&transactions);
+    let result = sensor.acc_self_test(&mut Delay).unwrap();
+
+    assert_eq!(result.xyz_delta(), (200, 400, 40));
+    assert_eq!(result.limits(), (68, 1440));
+    assert!(result.x_passed());
+    assert!(result.y_passed());
+    assert!(!result.z_passed());
+    assert!(!result.passed());
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn accel_self_test_times_out_without_data() {
+    let mut transactions = vec![
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG2_A, 0]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::FIFO_CTRL_REG_A, 0]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0b10000000]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, 5 << 4 | DEFAULT_CTRL_REG1_A],
+        ),
+    ];
+    transactions.extend(
+        (0..100).map(|_| I2cTrans::write_read(ACCEL_ADDR, vec![Register::STATUS_REG_A], vec![0])),
+    );
+    transactions.extend(accel_restore_txns());
+
+    let mut sensor = new_i2c(&transactions);
+    assert!(matches!(
+        sensor.acc_self_test(&mut Delay),
+        Err(Error::Timeout)
+    ));
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn accel_self_test_bypasses_high_pass_filter_and_fifo() {
+    let mut transactions = vec![
+        // Configure the high-pass filter and the FIFO.
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG2_A, 0b00001000]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG5_A, 0b01000000]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::FIFO_CTRL_REG_A, 0b10011111]),
+        // Self-test
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG2_A, 0]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::FIFO_CTRL_REG_A, 0b00011111]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0b10000000]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, 5 << 4 | DEFAULT_CTRL_REG1_A],
+        ),
+    ];
+    transactions.extend(accel_samples(-10, 0, 250));
+    transactions.push(I2cTrans::write(
+        ACCEL_ADDR,
+        vec![Register::CTRL_REG4_A, 0b10000010],
+    ));
+    transactions.extend(accel_samples(40, 100, 260));
+    transactions.extend([
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG2_A, 0b00001000]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::FIFO_CTRL_REG_A, 0b10011111]),
+    ]);
+
+    let mut sensor = new_i2c(&transactions);
+    sensor
+        .acc_enable_high_pass_filter(HighPassFilterTarget::OutputData)
+        .unwrap();
+    sensor.acc_set_fifo_mode(FifoMode::Stream, 31).unwrap();
+    sensor.acc_self_test(&mut Delay).unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_run_mag_self_test() {
+    let mut transactions = vec![
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_C_M, 0b00010000]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_B_M, 0b00000010]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 0b10001100]),
+        // No data available yet.
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0]),
+    ];
+    transactions.extend(mag_samples(100, -200, 300));
+    transactions.push(I2cTrans::write(
+        MAG_ADDR,
+        vec![Register::CFG_REG_C_M, 0b00010010],
+    ));
+    transactions.extend(mag_samples(150, -10, 290));
+    transactions.extend([
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_C_M, 0]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_B_M, 0]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, DEFAULT_CFG_REG_A_M]),
+    ]);
+
+    let mut sensor = new_i2c(&transactions);
+    let result = sensor.mag_self_test(&mut Delay).unwrap();
+
+    assert_eq!(result.xyz_delta(), (50 * 150, 190 * 150, 10 * 150));
+    assert_eq!(result.limits(), (2250, 75000));
+    assert!(result.x_passed());
+    assert!(result.y_passed());
+    assert!(!result.z_passed());
+    assert!(!result.passed());
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn mag_self_test_times_out_without_data() {
+    let mut transactions = vec![
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_C_M, 0b00010000]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_B_M, 0b00000010]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 0b10001100]),
+    ];
+    transactions.extend(
+        (0..100).map(|_| I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0])),
+    );
+    transactions.extend([
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_C_M, 0]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_B_M, 0]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, DEFAULT_CFG_REG_A_M]),
+    ]);
+
+    let mut sensor = new_i2c(&transactions);
+    assert!(matches!(
+        sensor.mag_self_test(&mut Delay),
+        Err(Error::Timeout)
+    ));
+    destroy_i2c(sensor);
+}
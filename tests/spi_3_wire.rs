@@ -0,0 +1,70 @@
+mod common;
+use crate::common::{
+    default_cs, default_cs_n, destroy_spi, new_spi_3_wire, BitFlags as BF, Register,
+    DEFAULT_CFG_REG_A_M,
+};
+use embedded_hal_mock::{
+    delay::MockNoop as Delay, pin::Mock as PinMock, spi::Transaction as SpiTrans,
+};
+
+#[test]
+fn can_init_3_wire() {
+    let mut sensor = new_spi_3_wire(
+        &[
+            SpiTrans::write(vec![Register::CTRL_REG4_A, BF::ACCEL_BDU | BF::SIM]),
+            SpiTrans::write(vec![Register::TEMP_CFG_REG_A, BF::TEMP_EN0 | BF::TEMP_EN1]),
+            SpiTrans::write(vec![Register::CFG_REG_C_M, BF::MAG_BDU | BF::I2C_DIS]),
+            SpiTrans::write(vec![
+                Register::CFG_REG_A_M,
+                DEFAULT_CFG_REG_A_M | BF::COMP_TEMP_EN,
+            ]),
+        ],
+        default_cs_n(2),
+        default_cs_n(2),
+    );
+    sensor.init().unwrap();
+    destroy_spi(sensor);
+}
+
+#[test]
+fn can_read_register_3_wire() {
+    let mut sensor = new_spi_3_wire(
+        &[
+            SpiTrans::write(vec![BF::SPI_RW | Register::WHO_AM_I_M]),
+            SpiTrans::transfer(vec![0], vec![0x40]),
+        ],
+        PinMock::new(&[]),
+        default_cs(),
+    );
+    assert!(sensor.magnetometer_id().unwrap().is_correct());
+    destroy_spi(sensor);
+}
+
+#[test]
+fn can_read_acceleration_3_wire() {
+    let mut sensor = new_spi_3_wire(
+        &[
+            SpiTrans::write(vec![BF::SPI_RW | BF::SPI_MS | Register::OUT_X_L_A]),
+            SpiTrans::transfer(vec![0; 6], vec![0x10, 0x00, 0x20, 0x00, 0x30, 0x00]),
+        ],
+        default_cs(),
+        PinMock::new(&[]),
+    );
+    let data = sensor.acceleration().unwrap();
+    assert_eq!(data.xyz_raw(), (0x10, 0x20, 0x30));
+    destroy_spi(sensor);
+}
+
+#[test]
+fn mag_soft_reset_keeps_i2c_disabled() {
+    let sensor = new_spi_3_wire(
+        &[
+            SpiTrans::write(vec![Register::CFG_REG_A_M, 0b0010_0011]),
+            SpiTrans::write(vec![Register::CFG_REG_C_M, BF::I2C_DIS]),
+        ],
+        PinMock::new(&[]),
+        default_cs_n(2),
+    );
+    let sensor = sensor.mag_soft_reset(&mut Delay).unwrap();
+    destroy_spi(sensor);
+}
@@ -0,0 +1,135 @@
+mod common;
+use crate::common::{
+    default_cs_n, destroy_i2c, destroy_spi, new_i2c, BitFlags as BF, Register, ACCEL_ADDR, MAG_ADDR,
+};
+use embedded_hal_mock::{
+    i2c::{Mock as I2cMock, Transaction as I2cTrans},
+    spi::{Mock as SpiMock, Transaction as SpiTrans},
+};
+use lsm303agr::{AccelMode, AccelScale, AnyMode, Lsm303agr};
+
+fn read_registers(ctrl_reg1_a: u8, ctrl_reg4_a: u8, cfg_reg_a_m: u8) -> Vec<I2cTrans> {
+    vec![
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CTRL_REG1_A], vec![ctrl_reg1_a]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CTRL_REG2_A], vec![0]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CTRL_REG3_A], vec![0]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CTRL_REG4_A], vec![ctrl_reg4_a]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CTRL_REG5_A], vec![0]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CTRL_REG6_A], vec![0]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::TEMP_CFG_REG_A], vec![0]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::FIFO_CTRL_REG_A], vec![0]),
+        I2cTrans::write_read(MAG_ADDR, vec![Register::CFG_REG_A_M], vec![cfg_reg_a_m]),
+        I2cTrans::write_read(MAG_ADDR, vec![Register::CFG_REG_B_M], vec![0]),
+        I2cTrans::write_read(MAG_ADDR, vec![Register::CFG_REG_C_M], vec![0]),
+    ]
+}
+
+#[test]
+fn can_sync_from_device() {
+    let mut transactions = read_registers(0x57, 0b10001000, 0b11);
+    // Scale change keeps BDU and HR.
+    transactions.push(I2cTrans::write(
+        ACCEL_ADDR,
+        vec![Register::CTRL_REG4_A, 0b10011000],
+    ));
+
+    let mut sensor = new_i2c(&transactions);
+    sensor.sync_from_device().unwrap();
+    assert_eq!(sensor.get_accel_mode(), AccelMode::HighResolution);
+    assert_eq!(sensor.get_accel_scale(), AccelScale::G2);
+    sensor.set_accel_scale(AccelScale::G4).unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_create_i2c_from_device_in_continuous_mode() {
+    let transactions = read_registers(0x07, 0, 0b1100);
+
+    match Lsm303agr::new_with_i2c_from_device(I2cMock::new(&transactions)).unwrap() {
+        AnyMode::MagContinuous(sensor) => destroy_i2c(sensor),
+        AnyMode::MagOneShot(_) => panic!("expected continuous mode"),
+    }
+}
+
+#[test]
+fn can_create_i2c_from_device_in_one_shot_mode() {
+    let transactions = read_registers(0x07, 0, 0b11);
+
+    match Lsm303agr::new_with_i2c_from_device(I2cMock::new(&transactions)).unwrap() {
+        AnyMode::MagOneShot(sensor) => destroy_i2c(sensor),
+        AnyMode::MagContinuous(_) => panic!("expected one-shot mode"),
+    }
+}
+
+#[test]
+fn can_create_spi_from_device() {
+    let mut transactions = [
+        Register::CTRL_REG1_A,
+        Register::CTRL_REG2_A,
+        Register::CTRL_REG3_A,
+        Register::CTRL_REG4_A,
+        Register::CTRL_REG5_A,
+        Register::CTRL_REG6_A,
+        Register::TEMP_CFG_REG_A,
+        Register::FIFO_CTRL_REG_A,
+    ]
+    .iter()
+    .map(|reg| SpiTrans::transfer(vec![0x80 | reg, 0], vec![0, 0]))
+    .collect::<Vec<_>>();
+    transactions.extend([
+        SpiTrans::transfer(vec![0x80 | Register::CFG_REG_A_M, 0], vec![0, 0]),
+        SpiTrans::transfer(vec![0x80 | Register::CFG_REG_B_M, 0], vec![0, 0]),
+        SpiTrans::transfer(vec![0x80 | Register::CFG_REG_C_M, 0], vec![0, 0]),
+    ]);
+
+    match Lsm303agr::new_with_spi_from_device(
+        SpiMock::new(&transactions),
+        default_cs_n(8),
+        default_cs_n(3),
+    )
+    .unwrap()
+    {
+        AnyMode::MagContinuous(sensor) => destroy_spi(sensor),
+        AnyMode::MagOneShot(_) => panic!("expected continuous mode"),
+    }
+}
+
+#[test]
+fn init_disables_mag_i2c_after_creating_spi_from_device() {
+    let mut transactions = [
+        Register::CTRL_REG1_A,
+        Register::CTRL_REG2_A,
+        Register::CTRL_REG3_A,
+        Register::CTRL_REG4_A,
+        Register::CTRL_REG5_A,
+        Register::CTRL_REG6_A,
+        Register::TEMP_CFG_REG_A,
+        Register::FIFO_CTRL_REG_A,
+    ]
+    .iter()
+    .map(|reg| SpiTrans::transfer(vec![0x80 | reg, 0], vec![0, 0]))
+    .collect::<Vec<_>>();
+    transactions.extend([
+        SpiTrans::transfer(vec![0x80 | Register::CFG_REG_A_M, 0], vec![0, 0]),
+        SpiTrans::transfer(vec![0x80 | Register::CFG_REG_B_M, 0], vec![0, 0]),
+        SpiTrans::transfer(vec![0x80 | Register::CFG_REG_C_M, 0], vec![0, 0]),
+        SpiTrans::write(vec![Register::CTRL_REG4_A, BF::ACCEL_BDU]),
+        SpiTrans::write(vec![Register::TEMP_CFG_REG_A, BF::TEMP_EN1 | BF::TEMP_EN0]),
+        SpiTrans::write(vec![Register::CFG_REG_C_M, BF::MAG_BDU | BF::I2C_DIS]),
+        SpiTrans::write(vec![Register::CFG_REG_A_M, BF::COMP_TEMP_EN]),
+    ]);
+
+    match Lsm303agr::new_with_spi_from_device(
+        SpiMock::new(&transactions),
+        default_cs_n(10),
+        default_cs_n(5),
+    )
+    .unwrap()
+    {
+        AnyMode::MagContinuous(mut sensor) => {
+            sensor.init().unwrap();
+            destroy_spi(sensor);
+        }
+        AnyMode::MagOneShot(_) => panic!("expected continuous mode"),
+    }
+}
@@ -0,0 +1,167 @@
+mod common;
+use crate::common::{
+    default_cs_n, destroy_i2c, destroy_spi, new_i2c, new_spi_mag, Register, ACCEL_ADDR,
+    DEFAULT_CFG_REG_A_M, MAG_ADDR,
+};
+use embedded_hal_mock::{
+    delay::MockNoop as Delay, i2c::Transaction as I2cTrans, spi::Transaction as SpiTrans,
+};
+use lsm303agr::{AccelScale, Error, HardIronOffset};
+
+#[test]
+fn can_verify_writes_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0b00010000]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CTRL_REG4_A], vec![0b00010000]),
+    ]);
+    sensor.set_verify_writes(true);
+    sensor.set_accel_scale(AccelScale::G4).unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn verify_failure_returns_error_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0b00010000]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CTRL_REG4_A], vec![0b00110000]),
+    ]);
+    sensor.set_verify_writes(true);
+    match sensor.set_accel_scale(AccelScale::G4) {
+        Err(Error::VerifyFailed {
+            register,
+            expected,
+            actual,
+        }) => {
+            assert_eq!(register, Register::CTRL_REG4_A);
+            assert_eq!(expected, 0b00010000);
+            assert_eq!(actual, 0b00110000);
+        }
+        _ => panic!("expected verify error"),
+    }
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn verify_skips_mag_soft_reset() {
+    let mut sensor = new_i2c(&[I2cTrans::write(
+        MAG_ADDR,
+        vec![Register::CFG_REG_A_M, 0b00100011],
+    )]);
+    sensor.set_verify_writes(true);
+    let sensor = sensor.mag_soft_reset(&mut Delay).ok().unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn verify_skips_mag_soft_reset_from_non_default_configuration() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 0b10000011]),
+        I2cTrans::write_read(MAG_ADDR, vec![Register::CFG_REG_A_M], vec![0b10000011]),
+        // The reset restores the default value 0b00000011.
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 0b10100011]),
+    ]);
+    sensor.set_verify_writes(true);
+    sensor.mag_enable_temp_compensation().unwrap();
+    let sensor = sensor.mag_soft_reset(&mut Delay).ok().unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn verify_ignores_mode_in_single_mode() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0]),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::CFG_REG_A_M],
+            vec![DEFAULT_CFG_REG_A_M],
+        ),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 0b00000001]),
+        // Measurement already done, back in idle mode.
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::CFG_REG_A_M],
+            vec![DEFAULT_CFG_REG_A_M],
+        ),
+    ]);
+    sensor.set_verify_writes(true);
+    assert!(matches!(
+        sensor.magnetic_field(),
+        Err(nb::Error::WouldBlock)
+    ));
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_verify_3_double_register_writes_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            MAG_ADDR,
+            vec![Register::OFFSET_X_REG_L_M | 0x80, 1, 0, 2, 0, 3, 0],
+        ),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::OFFSET_X_REG_L_M | 0x80],
+            vec![1, 0, 2, 0, 4, 0],
+        ),
+    ]);
+    sensor.set_verify_writes(true);
+    match sensor.set_mag_hard_iron_offset(HardIronOffset::from_unscaled(1, 2, 3)) {
+        Err(Error::VerifyFailed {
+            register,
+            expected,
+            actual,
+        }) => {
+            assert_eq!(register, Register::OFFSET_X_REG_L_M + 4);
+            assert_eq!(expected, 3);
+            assert_eq!(actual, 4);
+        }
+        _ => panic!("expected verify error"),
+    }
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn does_not_verify_writes_by_default() {
+    let mut sensor = new_i2c(&[I2cTrans::write(
+        ACCEL_ADDR,
+        vec![Register::CTRL_REG4_A, 0b00010000],
+    )]);
+    sensor.set_accel_scale(AccelScale::G4).unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_verify_writes_spi() {
+    let mut sensor = new_spi_mag(
+        &[
+            SpiTrans::write(vec![Register::CFG_REG_B_M, 0b1]),
+            SpiTrans::transfer(vec![0x80 | Register::CFG_REG_B_M, 0], vec![0, 0]),
+        ],
+        default_cs_n(2),
+    );
+    sensor.set_verify_writes(true);
+    match sensor.mag_enable_low_pass_filter() {
+        Err(Error::VerifyFailed { register, .. }) => assert_eq!(register, Register::CFG_REG_B_M),
+        _ => panic!("expected verify error"),
+    }
+    destroy_spi(sensor);
+}
+
+#[test]
+fn can_verify_3_double_register_writes_spi() {
+    let mut sensor = new_spi_mag(
+        &[
+            SpiTrans::write(vec![0x40 | Register::OFFSET_X_REG_L_M, 1, 0, 2, 0, 3, 0]),
+            SpiTrans::transfer(
+                vec![0xC0 | Register::OFFSET_X_REG_L_M, 0, 0, 0, 0, 0, 0],
+                vec![0, 1, 0, 2, 0, 3, 0],
+            ),
+        ],
+        default_cs_n(2),
+    );
+    sensor.set_verify_writes(true);
+    sensor
+        .set_mag_hard_iron_offset(HardIronOffset::from_unscaled(1, 2, 3))
+        .unwrap();
+    destroy_spi(sensor);
+}